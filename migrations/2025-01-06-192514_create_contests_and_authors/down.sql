@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE "submissions" DROP COLUMN "author_id";
+ALTER TABLE "submissions" DROP COLUMN "contest_id";
+DROP TABLE IF EXISTS "authors";
+DROP TABLE IF EXISTS "contests";
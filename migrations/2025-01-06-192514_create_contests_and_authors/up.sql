@@ -0,0 +1,26 @@
+-- Your SQL goes here
+
+CREATE TABLE "contests"(
+	"id" SERIAL PRIMARY KEY,
+	"slug" TEXT NOT NULL UNIQUE,
+	"title" TEXT NOT NULL,
+	"publication" TEXT NOT NULL,
+	"starts_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	"ends_at" TIMESTAMP WITH TIME ZONE
+);
+
+INSERT INTO "contests"("slug", "title", "publication", "starts_at")
+VALUES ('transformation', 'Transformation', 'My Fair Lighthouse', '2024-12-15 00:00:00+00');
+
+CREATE TABLE "authors"(
+	"id" TEXT NOT NULL PRIMARY KEY,
+	"name" TEXT NOT NULL,
+	"username" TEXT NOT NULL,
+	"bio" TEXT NOT NULL,
+	"image_id" TEXT NOT NULL,
+	"follower_count" INTEGER NOT NULL,
+	"details_last_updated_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+ALTER TABLE "submissions" ADD COLUMN "contest_id" INTEGER NOT NULL DEFAULT 1 REFERENCES contests(id);
+ALTER TABLE "submissions" ADD COLUMN "author_id" TEXT REFERENCES authors(id);
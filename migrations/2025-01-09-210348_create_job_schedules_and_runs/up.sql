@@ -0,0 +1,24 @@
+-- Your SQL goes here
+
+CREATE TABLE "job_schedules"(
+	"name" TEXT NOT NULL PRIMARY KEY,
+	"interval_seconds" INTEGER NOT NULL,
+	"enabled" BOOLEAN NOT NULL DEFAULT TRUE
+);
+
+INSERT INTO "job_schedules"("name", "interval_seconds") VALUES
+	('update_rss', 3600),
+	('update_story_details', 86400),
+	('update_claps', 900);
+
+CREATE TABLE "job_runs"(
+	"id" SERIAL PRIMARY KEY,
+	"job_name" TEXT NOT NULL,
+	"started_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	"finished_at" TIMESTAMP WITH TIME ZONE,
+	"status" INT2 NOT NULL DEFAULT 0,
+	"items_processed" INTEGER NOT NULL DEFAULT 0,
+	"error" TEXT
+);
+
+CREATE INDEX "job_runs_job_name_started_at_idx" ON "job_runs"("job_name", "started_at" DESC);
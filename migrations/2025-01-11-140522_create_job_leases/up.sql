@@ -0,0 +1,8 @@
+-- Your SQL goes here
+
+CREATE TABLE "job_leases"(
+	"job_name" TEXT NOT NULL PRIMARY KEY,
+	"holder" TEXT NOT NULL,
+	"acquired_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	"expires_at" TIMESTAMP WITH TIME ZONE NOT NULL
+);
@@ -0,0 +1,12 @@
+-- Your SQL goes here
+
+CREATE TABLE "ingest_errors"(
+	"id" SERIAL PRIMARY KEY,
+	"job_name" TEXT NOT NULL,
+	"guid" TEXT,
+	"detail" TEXT NOT NULL,
+	"error" TEXT NOT NULL,
+	"created_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX "ingest_errors_created_at_idx" ON "ingest_errors"("created_at" DESC);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+DELETE FROM "job_schedules" WHERE "name" = 'backfill_archive';
+DROP TABLE IF EXISTS "backfill_progress";
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+
+CREATE TABLE "backfill_progress"(
+	"contest_id" INTEGER NOT NULL PRIMARY KEY REFERENCES contests(id),
+	"cursor" TEXT,
+	"pages_fetched" INTEGER NOT NULL DEFAULT 0,
+	"completed_at" TIMESTAMP WITH TIME ZONE,
+	"updated_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO "job_schedules"("name", "interval_seconds") VALUES ('backfill_archive', 21600);
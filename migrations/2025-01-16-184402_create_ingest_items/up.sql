@@ -0,0 +1,14 @@
+-- Your SQL goes here
+
+CREATE TABLE "ingest_items"(
+	"guid" TEXT PRIMARY KEY,
+	"job_name" TEXT NOT NULL,
+	"status" SMALLINT NOT NULL,
+	"failure" SMALLINT,
+	"attempts" INTEGER NOT NULL DEFAULT 0,
+	"last_error" TEXT,
+	"next_attempt_at" TIMESTAMP WITH TIME ZONE,
+	"updated_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX "ingest_items_status_idx" ON "ingest_items"("status", "next_attempt_at");
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+
+ALTER TABLE "ingest_errors" ADD COLUMN "raw_response" TEXT;
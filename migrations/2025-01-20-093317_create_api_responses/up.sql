@@ -0,0 +1,13 @@
+-- Your SQL goes here
+
+-- Bodies are stored as they came from Medium, Postgres compresses large values on its own.
+CREATE TABLE "api_responses"(
+	"id" SERIAL PRIMARY KEY,
+	"kind" TEXT NOT NULL,
+	"request_key" TEXT NOT NULL,
+	"body" BYTEA NOT NULL,
+	"fetched_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX "api_responses_request_idx" ON "api_responses"("kind", "request_key", "fetched_at" DESC);
+CREATE INDEX "api_responses_fetched_at_idx" ON "api_responses"("fetched_at");
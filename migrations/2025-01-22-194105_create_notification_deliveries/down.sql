@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+DELETE FROM "job_schedules" WHERE "name" = 'deliver_notifications';
+DROP TABLE IF EXISTS "notification_deliveries";
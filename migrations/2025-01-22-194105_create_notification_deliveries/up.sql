@@ -0,0 +1,21 @@
+-- Your SQL goes here
+
+CREATE TABLE "notification_deliveries"(
+	"id" SERIAL PRIMARY KEY,
+	"target" TEXT NOT NULL,
+	"event" TEXT NOT NULL,
+	-- Identifies the announcement, so the same event is never sent twice to a target.
+	"dedup_key" TEXT NOT NULL,
+	"message" TEXT NOT NULL,
+	"status" SMALLINT NOT NULL DEFAULT 0,
+	"attempts" INTEGER NOT NULL DEFAULT 0,
+	"last_error" TEXT,
+	"next_attempt_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	"created_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	"delivered_at" TIMESTAMP WITH TIME ZONE,
+	UNIQUE ("target", "dedup_key")
+);
+
+CREATE INDEX "notification_deliveries_pending_idx" ON "notification_deliveries"("status", "next_attempt_at");
+
+INSERT INTO "job_schedules"("name", "interval_seconds") VALUES ('deliver_notifications', 60);
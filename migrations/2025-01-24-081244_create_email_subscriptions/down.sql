@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+DELETE FROM "job_schedules" WHERE "name" = 'send_email_digests';
+DROP TABLE IF EXISTS "subscription_requests";
+DROP TABLE IF EXISTS "email_subscriptions";
@@ -0,0 +1,30 @@
+-- Your SQL goes here
+
+CREATE TABLE "email_subscriptions"(
+	"id" SERIAL PRIMARY KEY,
+	"username" TEXT NOT NULL,
+	"email" TEXT NOT NULL,
+	-- Secret in the confirmation and unsubscribe links.
+	"token" TEXT NOT NULL UNIQUE DEFAULT gen_random_uuid()::text,
+	"created_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	-- When the last confirmation link was sent, links expire after a day.
+	"confirmation_sent_at" TIMESTAMP WITH TIME ZONE,
+	"confirmed_at" TIMESTAMP WITH TIME ZONE,
+	"unsubscribed_at" TIMESTAMP WITH TIME ZONE,
+	"last_sent_at" TIMESTAMP WITH TIME ZONE,
+	UNIQUE ("username", "email")
+);
+
+-- Every confirmation link sent, to throttle subscriptions per address and per IP.
+CREATE TABLE "subscription_requests"(
+	"id" SERIAL PRIMARY KEY,
+	"email" TEXT NOT NULL,
+	-- NULL when the client address is not known.
+	"ip" TEXT,
+	"created_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX "subscription_requests_email_idx" ON "subscription_requests"("email", "created_at");
+CREATE INDEX "subscription_requests_ip_idx" ON "subscription_requests"("ip", "created_at");
+
+INSERT INTO "job_schedules"("name", "interval_seconds") VALUES ('send_email_digests', 86400);
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+
+ALTER TABLE "notification_deliveries" ADD COLUMN "image_url" TEXT;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE "submissions" DROP COLUMN "voter_count";
+ALTER TABLE "contests" DROP COLUMN "scoring";
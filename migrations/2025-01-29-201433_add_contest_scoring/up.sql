@@ -0,0 +1,6 @@
+-- Your SQL goes here
+
+-- Serialized `Scoring` of the contest.
+ALTER TABLE "contests" ADD COLUMN "scoring" TEXT NOT NULL DEFAULT '{"kind":"claps"}';
+-- Readers who clapped, only fetched for contests scored by unique clappers.
+ALTER TABLE "submissions" ADD COLUMN "voter_count" INTEGER;
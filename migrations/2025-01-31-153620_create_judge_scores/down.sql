@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE "contests" DROP COLUMN "jury_published_at";
+DROP TABLE "judge_scores";
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+
+CREATE TABLE "judge_scores"(
+	"id" SERIAL PRIMARY KEY,
+	"guid" TEXT NOT NULL REFERENCES submissions(guid) ON DELETE CASCADE,
+	"judge" TEXT NOT NULL,
+	"criterion" TEXT NOT NULL,
+	"score" INTEGER NOT NULL,
+	"comment" TEXT NOT NULL DEFAULT '',
+	"updated_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	UNIQUE ("guid", "judge", "criterion")
+);
+
+-- Individual scores and comments are public once this is set.
+ALTER TABLE "contests" ADD COLUMN "jury_published_at" TIMESTAMP WITH TIME ZONE;
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE "login_requests";
+DROP TABLE "votes";
+DROP TABLE "readers";
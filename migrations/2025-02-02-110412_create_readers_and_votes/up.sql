@@ -0,0 +1,40 @@
+-- Your SQL goes here
+
+CREATE TABLE "readers"(
+	"id" SERIAL PRIMARY KEY,
+	"email" TEXT NOT NULL UNIQUE,
+	-- Secret in the emailed login link, replaced on every login request.
+	"login_token" TEXT UNIQUE,
+	"login_requested_at" TIMESTAMP WITH TIME ZONE,
+	-- Secret in the session cookie.
+	"session_token" TEXT UNIQUE,
+	"verified_at" TIMESTAMP WITH TIME ZONE,
+	"created_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE "votes"(
+	"id" SERIAL PRIMARY KEY,
+	"reader_id" INTEGER NOT NULL REFERENCES readers(id) ON DELETE CASCADE,
+	"guid" TEXT NOT NULL REFERENCES submissions(guid) ON DELETE CASCADE,
+	"contest_id" INTEGER NOT NULL REFERENCES contests(id),
+	"category" SMALLINT NOT NULL,
+	-- NULL when the client address is not known.
+	"ip" TEXT,
+	-- Why the fraud check held the vote back. Flagged votes are not counted.
+	"flag" TEXT,
+	"created_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+	UNIQUE ("reader_id", "guid")
+);
+
+CREATE INDEX "votes_ip_idx" ON "votes"("contest_id", "ip");
+
+-- Every login link sent, to throttle requests per address and per IP.
+CREATE TABLE "login_requests"(
+	"id" SERIAL PRIMARY KEY,
+	"email" TEXT NOT NULL,
+	"ip" TEXT,
+	"created_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX "login_requests_email_idx" ON "login_requests"("email", "created_at");
+CREATE INDEX "login_requests_ip_idx" ON "login_requests"("ip", "created_at");
@@ -0,0 +1,421 @@
+use crate::components::manual_submission_form::ManualSubmissionForm;
+use crate::components::scoring_selector::ScoringSelector;
+use crate::models::{
+    DeliveryStatus, IngestError, IngestItem, ItemStatus, JobOverview, JobStatus, NotificationDelivery, Vote,
+};
+use crate::server_functions::*;
+use dioxus::logger::tracing;
+use dioxus::prelude::*;
+
+const TIME_FMT: &str = "%d.%m. %H:%M:%S";
+const INGEST_ERROR_LIMIT: i64 = 100;
+const DELIVERY_LIMIT: i64 = 50;
+const FLAGGED_VOTE_LIMIT: i64 = 100;
+
+#[component]
+pub fn AdminPage() -> Element {
+    let mut draft_token = use_signal(String::new);
+    let mut token = use_signal(String::new);
+
+    let mut jobs = use_resource(move || async move {
+        if token().is_empty() {
+            return Ok(Vec::new());
+        }
+        get_job_overview(token()).await
+    });
+    let errors = use_resource(move || async move {
+        if token().is_empty() {
+            return Ok(Vec::new());
+        }
+        get_ingest_errors(token(), INGEST_ERROR_LIMIT).await
+    });
+    let failed_items = use_resource(move || async move {
+        if token().is_empty() {
+            return Ok(Vec::new());
+        }
+        get_failed_items(token()).await
+    });
+    let deliveries = use_resource(move || async move {
+        if token().is_empty() {
+            return Ok(Vec::new());
+        }
+        get_notification_deliveries(token(), DELIVERY_LIMIT).await
+    });
+    let flagged_votes = use_resource(move || async move {
+        if token().is_empty() {
+            return Ok(Vec::new());
+        }
+        get_flagged_votes(token(), FLAGGED_VOTE_LIMIT).await
+    });
+
+    let (jobs_overview, ingest_errors) = match (&*jobs.read_unchecked(), &*errors.read_unchecked()) {
+        (Some(Ok(jobs)), Some(Ok(errors))) => (jobs.clone(), errors.clone()),
+        (Some(Err(err)), _) | (_, Some(Err(err))) => {
+            tracing::error!("{}", err);
+            (Vec::new(), Vec::new())
+        }
+        _ => (Vec::new(), Vec::new()),
+    };
+    let (skipped_items, fetch_errors): (Vec<IngestError>, Vec<IngestError>) =
+        ingest_errors
+            .into_iter()
+            .partition(|error| error.job_name == "update_rss" && error.guid.is_none());
+    let failed_items = match &*failed_items.read_unchecked() {
+        Some(Ok(items)) => items.clone(),
+        Some(Err(err)) => {
+            tracing::error!("{}", err);
+            Vec::new()
+        }
+        None => Vec::new(),
+    };
+    let deliveries = match &*deliveries.read_unchecked() {
+        Some(Ok(deliveries)) => deliveries.clone(),
+        Some(Err(err)) => {
+            tracing::error!("{}", err);
+            Vec::new()
+        }
+        None => Vec::new(),
+    };
+
+    let flagged_votes = match &*flagged_votes.read_unchecked() {
+        Some(Ok(votes)) => votes.clone(),
+        Some(Err(err)) => {
+            tracing::error!("{}", err);
+            Vec::new()
+        }
+        None => Vec::new(),
+    };
+
+    rsx! {
+        document::Title{"Admin"}
+        document::Meta{ name: "robots", content: "noindex" }
+        div{class: "container py-4",
+            h1{"Admin"}
+            form{class: "row g-2 my-3",
+                onsubmit: move |ev| {
+                    ev.prevent_default();
+                    token.set(draft_token());
+                },
+                div{class: "col-auto",
+                    input{
+                        class: "form-control",
+                        r#type: "password",
+                        placeholder: "Admin token",
+                        value: "{draft_token}",
+                        oninput: move |ev| draft_token.set(ev.value()),
+                    }
+                }
+                div{class: "col-auto",
+                    button{class: "btn btn-primary", r#type: "submit", "Unlock"}
+                }
+            }
+
+            h4{class: "mt-4", "Scheduled jobs"}
+            table{class: "table table-hover table-striped table-sm",
+                thead{
+                    tr{
+                        th{"Job"}
+                        th{"Interval"}
+                        th{"Last run"}
+                        th{"Status"}
+                        th{"Duration"}
+                        th{"Items"}
+                        th{"Next run"}
+                        th{}
+                    }
+                }
+                tbody{
+                    for job in jobs_overview{
+                        JobRow{
+                            job: job.clone(),
+                            on_trigger: move |name: String| async move {
+                                match trigger_job(token(), name).await {
+                                    Ok(()) => jobs.restart(),
+                                    Err(err) => tracing::error!("{}", err),
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            h4{class: "mt-4", "Scoring"}
+            ScoringSelector{token}
+
+            h4{class: "mt-4", "Jury"}
+            JuryOverview{token}
+
+            h4{class: "mt-4", "Add a submission"}
+            ManualSubmissionForm{token}
+
+            h4{class: "mt-4", "Failed posts"}
+            FailedItemTable{items: failed_items}
+
+            h4{class: "mt-4", "Fetch errors"}
+            IngestErrorTable{errors: fetch_errors}
+
+            h4{class: "mt-4", "Skipped RSS items"}
+            IngestErrorTable{errors: skipped_items}
+
+            h4{class: "mt-4", "Notifications"}
+            DeliveryTable{deliveries}
+
+            h4{class: "mt-4", "Flagged votes"}
+            FlaggedVoteTable{votes: flagged_votes}
+        }
+    }
+}
+
+#[component]
+fn JobRow(job: JobOverview, on_trigger: EventHandler<String>) -> Element {
+    let status = match (&job.last_run, job.running) {
+        (_, true) => "running",
+        (None, false) => "never run",
+        (Some(run), false) => match run.status {
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Failed => "failed",
+        },
+    };
+    let duration = job
+        .last_run
+        .as_ref()
+        .and_then(|run| run.finished_at.map(|finished_at| finished_at - run.started_at))
+        .map_or("---".to_string(), |duration| format!("{}s", duration.num_seconds()));
+    let name = job.name.clone();
+
+    rsx! {
+        tr{
+            td{{job.name.clone()}}
+            td{{job.interval_seconds.map_or("disabled".to_string(), |seconds| format!("{}s", seconds))}}
+            td{{job.last_run.as_ref().map_or("---".to_string(), |run| run.started_at.format(TIME_FMT).to_string())}}
+            td{
+                title: job.last_run.as_ref().and_then(|run| run.error.clone()).unwrap_or_default(),
+                {status}
+            }
+            td{{duration}}
+            td{{job.last_run.as_ref().map_or("---".to_string(), |run| run.items_processed.to_string())}}
+            td{{job.next_run.map_or("---".to_string(), |next| next.format(TIME_FMT).to_string())}}
+            td{
+                button{
+                    class: "btn btn-sm btn-outline-secondary",
+                    disabled: job.running,
+                    onclick: move |_| on_trigger.call(name.clone()),
+                    "Run now"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn IngestErrorTable(errors: Vec<IngestError>) -> Element {
+    rsx! {
+        table{class: "table table-hover table-striped table-sm",
+            thead{
+                tr{
+                    th{"Time"}
+                    th{"Job"}
+                    th{"Guid"}
+                    th{"Item"}
+                    th{"Error"}
+                }
+            }
+            tbody{
+                for error in errors{
+                    tr{
+                        td{{error.created_at.format(TIME_FMT).to_string()}}
+                        td{{error.job_name}}
+                        td{{error.guid.unwrap_or_default()}}
+                        td{{error.detail}}
+                        td{
+                            {error.error}
+                            if let Some(raw_response) = error.raw_response{
+                                details{
+                                    summary{"Raw response"}
+                                    pre{class: "small", {raw_response}}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn FailedItemTable(items: Vec<IngestItem>) -> Element {
+    rsx! {
+        table{class: "table table-hover table-striped table-sm",
+            thead{
+                tr{
+                    th{"Guid"}
+                    th{"Job"}
+                    th{"Failure"}
+                    th{"Status"}
+                    th{"Attempts"}
+                    th{"Next attempt"}
+                    th{"Error"}
+                }
+            }
+            tbody{
+                for item in items{
+                    tr{
+                        td{{item.guid}}
+                        td{{item.job_name}}
+                        td{{item.failure.map_or("---", |failure| failure.name())}}
+                        td{
+                            {match item.status {
+                                ItemStatus::Retrying => "transient, retrying",
+                                ItemStatus::Failed => "permanent",
+                                ItemStatus::Ingested => "ingested",
+                            }}
+                        }
+                        td{{item.attempts.to_string()}}
+                        td{{item.next_attempt_at.map_or("---".to_string(), |next| next.format(TIME_FMT).to_string())}}
+                        td{{item.last_error.unwrap_or_default()}}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn DeliveryTable(deliveries: Vec<NotificationDelivery>) -> Element {
+    rsx! {
+        table{class: "table table-hover table-striped table-sm",
+            thead{
+                tr{
+                    th{"Queued"}
+                    th{"Target"}
+                    th{"Event"}
+                    th{"Status"}
+                    th{"Attempts"}
+                    th{"Message"}
+                    th{"Error"}
+                }
+            }
+            tbody{
+                for delivery in deliveries{
+                    tr{
+                        td{{delivery.created_at.format(TIME_FMT).to_string()}}
+                        td{{delivery.target}}
+                        td{{delivery.event}}
+                        td{
+                            {match (delivery.status, delivery.delivered_at) {
+                                (DeliveryStatus::Delivered, Some(at)) => format!("delivered {}", at.format(TIME_FMT)),
+                                (DeliveryStatus::Delivered, None) => "delivered".to_string(),
+                                (DeliveryStatus::Pending, _) => format!("pending, next {}", delivery.next_attempt_at.format(TIME_FMT)),
+                                (DeliveryStatus::Failed, _) => "gave up".to_string(),
+                            }}
+                        }
+                        td{{delivery.attempts.to_string()}}
+                        td{class: "text-break", {delivery.message}}
+                        td{{delivery.last_error.unwrap_or_default()}}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn FlaggedVoteTable(votes: Vec<(Vote, String)>) -> Element {
+    rsx! {
+        table{class: "table table-hover table-striped table-sm",
+            thead{
+                tr{
+                    th{"Time"}
+                    th{"Reader"}
+                    th{"Post"}
+                    th{"IP"}
+                    th{"Reason"}
+                }
+            }
+            tbody{
+                for (vote, email) in votes{
+                    tr{
+                        td{{vote.created_at.format(TIME_FMT).to_string()}}
+                        td{{email}}
+                        td{
+                            a{
+                                href: format!("https://medium.com/p/{}", vote.guid),
+                                target: "_blank",
+                                {vote.guid.clone()}
+                            }
+                        }
+                        td{{vote.ip.unwrap_or_else(|| "unknown".to_owned())}}
+                        td{{vote.flag.unwrap_or_default()}}
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Aggregated jury results, visible here before they are published.
+#[component]
+fn JuryOverview(token: Signal<String>) -> Element {
+    let mut overview = use_resource(move || async move {
+        if token().is_empty() {
+            return Ok(None);
+        }
+        get_jury_overview(token()).await.map(Some)
+    });
+
+    let (contest, results) = match &*overview.read_unchecked() {
+        Some(Ok(Some((contest, results)))) => (contest.clone(), results.clone()),
+        Some(Err(err)) => {
+            tracing::error!("{}", err);
+            return rsx! {};
+        }
+        _ => return rsx! {},
+    };
+    let published = contest.jury_published_at.is_some();
+
+    rsx! {
+        p{
+            {match contest.jury_published_at {
+                Some(published_at) => format!("Published {}.", published_at.format(TIME_FMT)),
+                None => "Not published, judges' scores and comments are hidden.".to_string(),
+            }}
+            button{
+                class: "btn btn-sm btn-outline-primary ms-2",
+                onclick: move |_| {
+                    let slug = contest.slug.clone();
+                    async move {
+                        match publish_jury_results(token(), slug, !published).await {
+                            Ok(()) => overview.restart(),
+                            Err(err) => tracing::error!("{}", err),
+                        }
+                    }
+                },
+                if published { "Unpublish" } else { "Publish results" }
+            }
+        }
+        table{class: "table table-hover table-striped table-sm",
+            thead{
+                tr{
+                    th{"Title"}
+                    th{"Category"}
+                    th{"Judges"}
+                    th{"Score"}
+                    th{"Criteria"}
+                }
+            }
+            tbody{
+                for (submission, result) in results{
+                    tr{
+                        td{{submission.title}}
+                        td{{submission.category.name()}}
+                        td{{result.judges.to_string()}}
+                        td{{format!("{:.1}", result.score)}}
+                        td{{result.criteria.iter().map(|(name, score)| format!("{} {:.1}", name, score)).collect::<Vec<_>>().join(" · ")}}
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::components::digest_subscription_form::DigestSubscriptionForm;
+use crate::components::page_meta::PageMeta;
+use crate::server_functions::get_author;
+use dioxus::logger::tracing;
+use dioxus::prelude::*;
+
+#[component]
+pub fn AuthorPage(id: ReadOnlySignal<String>) -> Element {
+    let author = use_server_future(move || async move { get_author(id()).await })?;
+
+    let (author, entries) = match &*author.read_unchecked() {
+        None => return rsx! { div{class: "container py-4", "Loading..."} },
+        Some(Err(err)) => {
+            tracing::error!("{}", err);
+            return rsx! { div{class: "container py-4", "Author not found."} };
+        }
+        Some(Ok((author, entries))) => (author.clone(), entries.clone()),
+    };
+
+    let json_ld = serde_json::json!({
+        "@context": "https://schema.org",
+        "@type": "ProfilePage",
+        "url": format!("/author/{}", author.id),
+        "mainEntity": {
+            "@type": "Person",
+            "name": author.name,
+            "alternateName": format!("@{}", author.username),
+            "description": author.bio,
+            "image": author.avatar_url(88),
+            "sameAs": format!("https://medium.com/@{}", author.username),
+        },
+    });
+
+    rsx! {
+        PageMeta{
+            title: author.name.clone(),
+            description: format!(
+                "{} (@{}) has {} contest entries with {} claps in total.",
+                author.name,
+                author.username,
+                entries.len(),
+                entries.iter().map(|(_, submission)| submission.clap_count).sum::<i32>()
+            ),
+            path: format!("/author/{}", author.id),
+            json_ld,
+        }
+        div{class: "container py-4",
+            div{class: "row align-items-center",
+                div{class: "col-auto",
+                    img{
+                        class: "rounded-circle",
+                        width: "88",
+                        height: "88",
+                        src: author.avatar_url(88),
+                    }
+                }
+                div{class: "col",
+                    h1{{author.name.clone()}}
+                    a{
+                        href: format!("https://medium.com/@{}", author.username),
+                        target: "_blank",
+                        rel: "noopener noreferrer",
+                        {format!("@{}", author.username)}
+                    }
+                    small{class: "ms-3", {format!("{} followers", author.follower_count)}}
+                    p{class: "mt-2", {author.bio.clone()}}
+                }
+            }
+            DigestSubscriptionForm{username: author.username.clone()}
+            table{class: "table mt-4 table-hover table-striped table-bordered",
+                thead{
+                    tr{
+                        th{"Contest"}
+                        th{"Title"}
+                        th{"Category"}
+                        th{"Claps"}
+                        th{"Word count"}
+                    }
+                }
+                tbody{
+                    class: "table-group-divider",
+                    for (contest, submission) in entries{
+                        tr{
+                            td{{contest.title}}
+                            td{
+                                a{
+                                    href: format!("https://medium.com/p/{}", submission.guid),
+                                    target: "_blank",
+                                    rel: "noopener noreferrer",
+                                    {submission.title}
+                                }
+                            }
+                            td{{submission.category.name()}}
+                            td{{submission.clap_count.to_string()}}
+                            td{{submission.word_count.to_string()}}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
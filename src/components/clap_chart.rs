@@ -5,10 +5,11 @@ use plotly::configuration::*;
 use plotly::color::Rgb;
 use plotly::layout::BarMode;
 use dioxus::logger::tracing;
-use crate::components::app::SubmissionsByCategory;
+use crate::components::home::SubmissionsByCategory;
 use dioxus::prelude::*;
 use serde::Serialize;
-use crate::models::Submission;
+use crate::models::{Category, ClapRace, Submission};
+use crate::server_functions::get_clap_race;
 #[cfg(feature = "web")]
 use web_sys::js_sys;
 
@@ -113,11 +114,153 @@ struct ScaleOptions {
                 }}
  */
 
+/// Entries per category in the animated chart.
+const RACE_LENGTH: usize = 10;
+/// Time a frame of the animated chart is shown at normal speed.
+const RACE_FRAME_MS: f64 = 500.0;
+/// Playback speeds offered for the animated chart.
+const RACE_SPEEDS: [f64; 4] = [0.5, 1.0, 2.0, 4.0];
+
+fn short_title(title: &str) -> String {
+    if title.chars().count() > 30 {
+        format!("{}…", title.chars().take(29).collect::<String>())
+    } else {
+        title.to_string()
+    }
+}
+
+/// Plotly figure of the race, one horizontal bar chart per category side by side, with one
+/// frame per moment of `race`.
+fn race_figure(race: &ClapRace) -> serde_json::Value {
+    let categories = [Category::Poetry, Category::Fiction, Category::PersonalEssay];
+
+    let traces = |clap_counts: &std::collections::HashMap<String, i32>| {
+        categories
+            .iter()
+            .enumerate()
+            .map(|(index, category)| {
+                let mut top: Vec<(&Submission, i32)> = race
+                    .submissions
+                    .iter()
+                    .filter(|sub| sub.category == *category)
+                    .filter_map(|sub| clap_counts.get(&sub.guid).map(|claps| (sub, *claps)))
+                    .collect();
+                top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.title.cmp(&b.0.title)));
+                top.truncate(RACE_LENGTH);
+                let axis = if index == 0 { String::new() } else { (index + 1).to_string() };
+                serde_json::json!({
+                    "type": "bar",
+                    "orientation": "h",
+                    "name": category.name(),
+                    "x": top.iter().map(|(_, claps)| claps).collect::<Vec<_>>(),
+                    "y": top.iter().map(|(sub, _)| short_title(&sub.title)).collect::<Vec<_>>(),
+                    "text": top.iter().map(|(_, claps)| claps).collect::<Vec<_>>(),
+                    "textposition": "outside",
+                    "xaxis": format!("x{}", axis),
+                    "yaxis": format!("y{}", axis),
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+    let title = |at: &chrono::DateTime<chrono::Local>| at.format("%Y-%m-%d %H:%M").to_string();
+
+    let mut layout = serde_json::json!({
+        "grid": { "rows": 1, "columns": categories.len(), "pattern": "independent" },
+        "paper_bgcolor": "rgb(20, 22, 26)",
+        "plot_bgcolor": "rgb(20, 22, 26)",
+        "title": { "text": race.frames.first().map(|frame| title(&frame.at)).unwrap_or_default() },
+        "margin": { "l": 200 },
+    });
+    // A fixed clap axis per category, so bars grow instead of the axis shrinking.
+    for (index, category) in categories.iter().enumerate() {
+        let max = race
+            .frames
+            .iter()
+            .flat_map(|frame| {
+                race.submissions
+                    .iter()
+                    .filter(|sub| sub.category == *category)
+                    .filter_map(move |sub| frame.clap_counts.get(&sub.guid))
+            })
+            .max()
+            .copied()
+            .unwrap_or(0);
+        let axis = if index == 0 { String::new() } else { (index + 1).to_string() };
+        layout[format!("xaxis{}", axis)] = serde_json::json!({ "range": [0, (max as f64 * 1.15).max(10.0)] });
+        layout[format!("yaxis{}", axis)] = serde_json::json!({ "automargin": true, "categoryorder": "total ascending" });
+    }
+
+    serde_json::json!({
+        "data": race.frames.first().map(|frame| traces(&frame.clap_counts)).unwrap_or_default(),
+        "layout": layout,
+        "frames": race.frames.iter().map(|frame| serde_json::json!({
+            "name": title(&frame.at),
+            "data": traces(&frame.clap_counts),
+            "layout": { "title": { "text": title(&frame.at) } },
+        })).collect::<Vec<_>>(),
+    })
+}
+
+/// Script drawing the first frame of `figure` and loading the others for playback.
+fn draw_script(id: &str, figure: &serde_json::Value) -> String {
+    format!(
+        r#"const figure = {figure};
+        await Plotly.newPlot("{id}", figure.data, figure.layout, {{displayModeBar: false}});
+        await Plotly.addFrames("{id}", figure.frames);
+        return true;"#
+    )
+}
+
+/// Script playing the frames, resolving once they are done or interrupted.
+fn play_script(id: &str, speed: f64, from_current: bool) -> String {
+    let duration = RACE_FRAME_MS / speed;
+    format!(
+        r#"try {{
+            await Plotly.animate("{id}", null, {{
+                frame: {{duration: {duration}, redraw: true}},
+                transition: {{duration: {transition}, easing: "linear"}},
+                mode: "immediate",
+                fromcurrent: {from_current},
+            }});
+        }} catch (_) {{}}
+        return true;"#,
+        transition = duration * 0.8,
+    )
+}
+
+/// Script stopping the playback at the current frame.
+fn pause_script(id: &str) -> String {
+    format!(
+        r#"await Plotly.animate("{id}", [null], {{frame: {{duration: 0, redraw: false}}, transition: {{duration: 0}}, mode: "immediate"}});
+        return true;"#
+    )
+}
+
+/// Claps of the current contest's entries, either as a grouped bar chart of the current counts
+/// or, in race mode, animated from the clap history.
 #[component]
 pub fn ClapChart(id: String, submissions_by_category: Memo<Option<SubmissionsByCategory>>) -> Element {
     let plot_id = id.clone();
+    let chart_id = use_signal(|| id.clone());
+    let mut race = use_signal(|| false);
+    let mut playing = use_signal(|| false);
+    let mut finished = use_signal(|| false);
+    let mut speed = use_signal(|| 1.0);
+    // Bumped on every play and pause, so an interrupted playback does not reset the controls.
+    let mut run = use_signal(|| 0usize);
+
+    let clap_race = use_resource(move || async move {
+        if race() {
+            Some(get_clap_race().await)
+        } else {
+            None
+        }
+    });
 
     use_effect(move || {
+        if race() {
+            return;
+        }
         if let Some(subs) = &*submissions_by_category.read_unchecked() {
             let mut plot = Plot::new();
 
@@ -150,11 +293,110 @@ pub fn ClapChart(id: String, submissions_by_category: Memo<Option<SubmissionsByC
         }
     });
 
+    use_effect(move || {
+        if !race() {
+            return;
+        }
+        match &*clap_race.read() {
+            Some(Some(Ok(data))) => {
+                let script = draw_script(&chart_id(), &race_figure(data));
+                finished.set(false);
+                spawn(async move {
+                    if let Err(err) = document::eval(&script).await {
+                        tracing::error!("{:?}", err);
+                    }
+                });
+            }
+            Some(Some(Err(err))) => tracing::error!("{}", err),
+            _ => {}
+        }
+    });
+
+    let mut play = move |from_current: bool| {
+        run += 1;
+        let current = run();
+        playing.set(true);
+        finished.set(false);
+        let script = play_script(&chart_id(), speed(), from_current);
+        spawn(async move {
+            if let Err(err) = document::eval(&script).await {
+                tracing::error!("{:?}", err);
+            }
+            if run() == current {
+                playing.set(false);
+                finished.set(true);
+            }
+        });
+    };
+
+    let mut pause = move || {
+        run += 1;
+        playing.set(false);
+        let script = pause_script(&chart_id());
+        spawn(async move {
+            if let Err(err) = document::eval(&script).await {
+                tracing::error!("{:?}", err);
+            }
+        });
+    };
+
     rsx! {
         div {class: "box mx-6",
+            div {class: "d-flex align-items-center gap-2 mb-2",
+                div {class: "btn-group",
+                    button {
+                        class: if race() { "btn btn-sm btn-outline-secondary" } else { "btn btn-sm btn-outline-secondary active" },
+                        onclick: move |_| {
+                            if playing() {
+                                pause();
+                            }
+                            race.set(false);
+                        },
+                        "Current"
+                    }
+                    button {
+                        class: if race() { "btn btn-sm btn-outline-secondary active" } else { "btn btn-sm btn-outline-secondary" },
+                        onclick: move |_| race.set(true),
+                        "Race"
+                    }
+                }
+                if race() {
+                    button {
+                        class: "btn btn-sm btn-primary",
+                        disabled: !matches!(&*clap_race.read_unchecked(), Some(Some(Ok(_)))),
+                        onclick: move |_| {
+                            if playing() {
+                                pause();
+                            } else {
+                                play(!finished());
+                            }
+                        },
+                        if playing() { "Pause" } else { "Play" }
+                    }
+                    select {
+                        class: "form-select form-select-sm w-auto",
+                        onchange: move |event| {
+                            if let Ok(value) = event.value().parse::<f64>() {
+                                speed.set(value);
+                                // Continue at the new speed from the current frame.
+                                if playing() {
+                                    play(true);
+                                }
+                            }
+                        },
+                        for factor in RACE_SPEEDS {
+                            option {
+                                value: factor.to_string(),
+                                selected: factor == speed(),
+                                {format!("{}×", factor)}
+                            }
+                        }
+                    }
+                }
+            }
             div {
                 id: id,
             }
         }
     }
-}
\ No newline at end of file
+}
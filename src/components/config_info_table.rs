@@ -1,16 +1,21 @@
-use crate::server_functions::get_latest_and_next_update_time;
+use crate::server_functions::{get_latest_and_next_update_time, get_site_info};
 use dioxus::logger::tracing;
 use dioxus::prelude::*;
 
 #[component]
 pub fn ConfigInfoTable() -> Element {
     let latest_update_time = use_resource(get_latest_and_next_update_time);
+    let site_info = use_resource(get_site_info);
+    let version = match &*site_info.read_unchecked() {
+        Some(Ok(site_info)) => site_info.version.clone(),
+        _ => "...".to_string(),
+    };
     let time_fmt = "%H:%M";
     let (latest, next) = match &*latest_update_time.read_unchecked() {
         None => ("...".to_string(), "...".to_string()),
         Some(Ok((latest, next))) => (
-            latest.format(time_fmt).to_string(),
-            next.format(time_fmt).to_string(),
+            latest.map_or("never".to_string(), |latest| latest.format(time_fmt).to_string()),
+            next.map_or("---".to_string(), |next| next.format(time_fmt).to_string()),
         ),
         Some(Err(err)) => {
             tracing::error!("{}", err);
@@ -22,7 +27,7 @@ pub fn ConfigInfoTable() -> Element {
         table{class: "table table-hover table-striped table-sm",
             tr{
                 td{"Version"}
-                td{"0.0.1"}
+                td{{version}}
             }
             tr{
                 td{"Claps last updated"}
@@ -34,4 +39,4 @@ pub fn ConfigInfoTable() -> Element {
             }
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,144 @@
+use crate::components::page_meta::PageMeta;
+use crate::components::route::Route;
+use crate::components::standings_table::StandingsTable;
+use crate::models::{Category, Contest, JuryResult};
+use crate::server_functions::{get_contest_standings, get_jury_results};
+use dioxus::logger::tracing;
+use dioxus::prelude::*;
+use std::collections::HashMap;
+
+/// Published jury results by guid, empty before publication or on errors.
+fn jury_by_guid(jury: Resource<Result<Vec<JuryResult>, ServerFnError>>) -> HashMap<String, JuryResult> {
+    match &*jury.read_unchecked() {
+        Some(Ok(results)) => results.iter().map(|result| (result.guid.clone(), result.clone())).collect(),
+        Some(Err(err)) => {
+            tracing::error!("{}", err);
+            HashMap::new()
+        }
+        None => HashMap::new(),
+    }
+}
+
+/// Schema.org description of the contest, shared by the home and contest pages.
+pub fn contest_json_ld(contest: &Contest) -> serde_json::Value {
+    serde_json::json!({
+        "@context": "https://schema.org",
+        "@type": "Event",
+        "name": contest.title,
+        "url": format!("/contest/{}", contest.slug),
+        "startDate": contest.starts_at.to_rfc3339(),
+        "endDate": contest.ends_at.map(|ends_at| ends_at.to_rfc3339()),
+        "eventAttendanceMode": "https://schema.org/OnlineEventAttendanceMode",
+        "location": { "@type": "VirtualLocation", "url": "https://medium.com" },
+        "organizer": { "@type": "Organization", "name": contest.publication },
+    })
+}
+
+#[component]
+pub fn ContestPage(contest: ReadOnlySignal<String>) -> Element {
+    let standings = use_server_future(move || get_contest_standings(contest()))?;
+    let jury = use_server_future(move || get_jury_results(contest()))?;
+    let jury = jury_by_guid(jury);
+
+    let (contest, entries) = match &*standings.read_unchecked() {
+        None => return rsx! { div{class: "container py-4", "Loading..."} },
+        Some(Err(err)) => {
+            tracing::error!("{}", err);
+            return rsx! { div{class: "container py-4", "Contest not found."} };
+        }
+        Some(Ok((contest, entries))) => (contest.clone(), entries.clone()),
+    };
+
+    rsx! {
+        PageMeta{
+            title: contest.title.clone(),
+            description: format!(
+                "Standings of the {} writing contest by {}: {} entries ranked by {} on Medium.",
+                contest.title, contest.publication, entries.len(), contest.scoring().name().to_lowercase()
+            ),
+            path: format!("/contest/{}", contest.slug),
+            json_ld: contest_json_ld(&contest),
+        }
+        div{class: "container py-4",
+            h1{{contest.title.clone()}}
+            small{"A " em{{contest.publication.clone()}} " writing contest, ranked by " {contest.scoring().name().to_lowercase()}}
+            p{
+                Link{to: Route::TimeTravelPage{contest: contest.slug.clone()}, "Replay the standings over time"}
+            }
+
+            for category in Category::ALL.into_iter().filter(|category| *category != Category::None){
+                h4{class: "mt-4",
+                    Link{
+                        to: Route::CategoryPage{contest: contest.slug.clone(), category: category.slug().to_string()},
+                        {category.name()}
+                    }
+                }
+                StandingsTable{
+                    entries: entries.iter().filter(|entry| entry.submission.category == category).take(5).cloned().collect::<Vec<_>>(),
+                    jury: jury.clone(),
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn CategoryPage(contest: ReadOnlySignal<String>, category: String) -> Element {
+    let standings = use_server_future(move || get_contest_standings(contest()))?;
+    let jury = use_server_future(move || get_jury_results(contest()))?;
+    let jury = jury_by_guid(jury);
+    let Some(category) = Category::from_slug(&category) else {
+        return rsx! { div{class: "container py-4", "Category not found."} };
+    };
+
+    let (contest, entries) = match &*standings.read_unchecked() {
+        None => return rsx! { div{class: "container py-4", "Loading..."} },
+        Some(Err(err)) => {
+            tracing::error!("{}", err);
+            return rsx! { div{class: "container py-4", "Contest not found."} };
+        }
+        Some(Ok((contest, entries))) => (
+            contest.clone(),
+            entries.iter().filter(|entry| entry.submission.category == category).cloned().collect::<Vec<_>>(),
+        ),
+    };
+    let path = format!("/contest/{}/{}", contest.slug, category.slug());
+
+    let json_ld = serde_json::json!({
+        "@context": "https://schema.org",
+        "@type": "ItemList",
+        "name": format!("{} – {}", contest.title, category.name()),
+        "url": path,
+        "itemListOrder": "https://schema.org/ItemListOrderDescending",
+        "numberOfItems": entries.len(),
+        "itemListElement": entries.iter().map(|entry| serde_json::json!({
+            "@type": "ListItem",
+            "position": entry.rank,
+            "name": entry.submission.title,
+            "url": format!("/submission/{}", entry.submission.guid),
+        })).collect::<Vec<_>>(),
+    });
+
+    rsx! {
+        PageMeta{
+            title: format!("{} – {}", category.name(), contest.title),
+            description: match entries.first() {
+                Some(leader) => format!(
+                    "{} entries in {} of the {} contest. Leading: {} by {} with {} claps.",
+                    entries.len(), category.name(), contest.title, leader.submission.title, leader.submission.realname, leader.submission.clap_count
+                ),
+                None => format!("{} entries of the {} contest.", category.name(), contest.title),
+            },
+            path,
+            category,
+            json_ld,
+        }
+        div{class: "container py-4",
+            h1{{category.name()}}
+            Link{to: Route::ContestPage{contest: contest.slug.clone()}, {contest.title.clone()}}
+            div{class: "mt-4",
+                StandingsTable{entries, jury}
+            }
+        }
+    }
+}
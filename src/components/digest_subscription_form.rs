@@ -0,0 +1,50 @@
+use crate::server_functions::subscribe_to_digest;
+use dioxus::logger::tracing;
+use dioxus::prelude::*;
+
+/// Lets an author sign up for the daily email with their standing.
+#[component]
+pub fn DigestSubscriptionForm(username: String) -> Element {
+    let mut email = use_signal(String::new);
+    let mut message = use_signal(|| None::<String>);
+
+    rsx! {
+        form{class: "row g-2 my-3",
+            onsubmit: {
+                let username = username.clone();
+                move |ev: FormEvent| {
+                    let username = username.clone();
+                    async move {
+                        ev.prevent_default();
+                        match subscribe_to_digest(username, email()).await {
+                            Ok(()) => {
+                                email.set(String::new());
+                                message.set(Some("Check your inbox for the confirmation link.".to_string()));
+                            }
+                            Err(err) => {
+                                tracing::error!("{}", err);
+                                message.set(Some(err.to_string()));
+                            }
+                        }
+                    }
+                }
+            },
+            div{class: "col-auto",
+                input{
+                    class: "form-control",
+                    r#type: "email",
+                    placeholder: "Email address",
+                    value: "{email}",
+                    oninput: move |ev| email.set(ev.value()),
+                }
+            }
+            div{class: "col-auto",
+                button{class: "btn btn-outline-primary", r#type: "submit", "Get a daily digest"}
+            }
+        }
+
+        if let Some(message) = message(){
+            div{class: "alert alert-info", {message}}
+        }
+    }
+}
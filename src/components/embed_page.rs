@@ -0,0 +1,75 @@
+use crate::models::Category;
+use crate::server_functions::get_embed_leaderboard;
+use dioxus::logger::tracing;
+use dioxus::prelude::*;
+
+const EMBED_CSS: Asset = asset!("/assets/styling/embed.css");
+
+/// The top entries of one category for iframes on other sites. Loads nothing but its own small
+/// stylesheet. `theme` is `light` or `dark`, `accent` a hex color without `#`.
+#[component]
+pub fn EmbedPage(contest: ReadOnlySignal<String>, category: String, theme: String, accent: String) -> Element {
+    let category = Category::from_slug(&category);
+    let leaderboard = use_server_future(move || async move {
+        match category {
+            Some(category) => get_embed_leaderboard(contest(), category).await.map(Some),
+            None => Ok(None),
+        }
+    })?;
+
+    let class = if theme == "dark" { "embed dark" } else { "embed" };
+    // Only plain hex colors, anything else could break out of the style attribute.
+    let style = if (3..=8).contains(&accent.len()) && accent.chars().all(|c| c.is_ascii_hexdigit()) {
+        format!("--embed-accent: #{}", accent)
+    } else {
+        String::new()
+    };
+
+    let content = match &*leaderboard.read_unchecked() {
+        Some(Ok(Some((contest, leaders)))) => {
+            let category = category.unwrap_or_default();
+            rsx! {
+                h1{{format!("{} – {}", contest.title, category.name())}}
+                table{
+                    tbody{
+                        for entry in leaders.iter(){
+                            tr{
+                                td{class: "rank", {format!("{}.", entry.rank)}}
+                                td{
+                                    a{
+                                        href: format!("https://medium.com/p/{}", entry.submission.guid),
+                                        target: "_blank",
+                                        rel: "noopener noreferrer",
+                                        {entry.submission.title.clone()}
+                                    }
+                                    div{class: "author", {entry.submission.realname.clone()}}
+                                }
+                                td{class: "claps", title: entry.breakdown.clone(), {entry.short_score(contest.scoring())}}
+                            }
+                        }
+                    }
+                }
+                if leaders.is_empty(){
+                    p{"No entries yet."}
+                }
+                footer{
+                    "Live standings of the "
+                    {contest.publication.clone()}
+                    " contest"
+                }
+            }
+        }
+        Some(Ok(None)) => rsx! { p{"Unknown category."} },
+        Some(Err(err)) => {
+            tracing::error!("{}", err);
+            rsx! { p{"Leaderboard not available."} }
+        }
+        None => rsx! { p{"Loading..."} },
+    };
+
+    rsx! {
+        document::Link{ rel: "stylesheet", href: EMBED_CSS }
+        document::Meta{ name: "robots", content: "noindex" }
+        div{class, style, {content}}
+    }
+}
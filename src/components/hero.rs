@@ -1,14 +1,21 @@
 use crate::components::config_info_table::ConfigInfoTable;
+use crate::server_functions::get_site_info;
 use dioxus::prelude::*;
 
 #[component]
 pub fn Hero() -> Element {
+    let site_info = use_resource(get_site_info);
+    let (title, publication) = match &*site_info.read_unchecked() {
+        Some(Ok(site_info)) => (site_info.contest.title.clone(), site_info.contest.publication.clone()),
+        _ => ("...".to_string(), "...".to_string()),
+    };
+
     rsx! {
         div{class: "container py-4",
             div{class: "row align-items-center",
                 div{class: "col",
-                    h1{"Transformation"}
-                    small{"A " em{"My Fair Lighthouse"} " writing contest"}
+                    h1{{title}}
+                    small{"A " em{{publication}} " writing contest"}
                 }
                 div{class: "col-2 small",
                     ConfigInfoTable{}
@@ -0,0 +1,149 @@
+use crate::components::clap_chart::ClapChart;
+use crate::components::contest_page::contest_json_ld;
+use crate::components::hero::Hero;
+use crate::components::leaderboard_table::*;
+use crate::components::page_meta::PageMeta;
+use crate::models::{Category, ScoredSubmission, Scoring, Submission};
+use crate::server_functions::*;
+use dioxus::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Default, PartialEq, Clone)]
+pub struct SubmissionsByCategory {
+    pub unsorted: Vec<Submission>,
+    pub poetry: Vec<Submission>,
+    pub fiction: Vec<Submission>,
+    pub essay: Vec<Submission>,
+    /// Strategy of the current contest.
+    pub scoring: Scoring,
+    /// Score and rank by guid, for entries of the current contest.
+    pub scores: HashMap<String, ScoredSubmission>,
+}
+
+/// All submissions, the strategy of the current contest and its ranked entries.
+type Leaderboard = (Vec<Submission>, Scoring, Vec<ScoredSubmission>);
+
+fn get_submissions_by_category(
+    submission_elements: Resource<Result<Leaderboard, ServerFnError>>,
+) -> Option<SubmissionsByCategory> {
+    if let Some(Ok((all_submissions, scoring, scored))) = &*submission_elements.read_unchecked() {
+        // Ranked entries first, then entries of older contests by claps.
+        let rank: HashMap<&str, usize> = scored
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| (entry.submission.guid.as_str(), i))
+            .collect();
+        let mut all_submissions = all_submissions.clone();
+        all_submissions.sort_by_key(|sub| rank.get(sub.guid.as_str()).copied().unwrap_or(usize::MAX));
+
+        Some(SubmissionsByCategory {
+            scoring: *scoring,
+            scores: scored
+                .iter()
+                .map(|entry| (entry.submission.guid.clone(), entry.clone()))
+                .collect(),
+            unsorted: all_submissions
+                .iter()
+                .filter(|sub| sub.category == Category::None)
+                .cloned()
+                .collect(),
+            poetry: all_submissions
+                .iter()
+                .filter(|sub| sub.category == Category::Poetry)
+                .cloned()
+                .collect(),
+            fiction: all_submissions
+                .iter()
+                .filter(|sub| sub.category == Category::Fiction)
+                .cloned()
+                .collect(),
+            essay: all_submissions
+                .iter()
+                .filter(|sub| sub.category == Category::PersonalEssay)
+                .cloned()
+                .collect(),
+        })
+    } else {
+        None
+    }
+}
+
+#[component]
+pub fn Home() -> Element {
+    let dragged_guid = use_signal(|| None);
+
+    let submissions_by_category = {
+        let submission_elements = use_resource(move || async move {
+            dragged_guid.read();
+            let all_submissions = get_all_submissions().await?;
+            let (contest, scored) = get_scored_submissions().await?;
+            Ok((all_submissions, contest.scoring(), scored))
+        });
+        use_memo(move || get_submissions_by_category(submission_elements))
+    };
+    let site_info = use_server_future(get_site_info)?;
+    let contest = match &*site_info.read_unchecked() {
+        Some(Ok(site_info)) => Some(site_info.contest.clone()),
+        _ => None,
+    };
+
+    rsx! {
+        if let Some(contest) = contest{
+            PageMeta{
+                title: contest.title.clone(),
+                description: format!(
+                    "Live leaderboard of the {} writing contest by {}, ranked by {} on Medium.",
+                    contest.title, contest.publication, contest.scoring().name().to_lowercase()
+                ),
+                path: "/".to_string(),
+                json_ld: contest_json_ld(&contest),
+            }
+        }
+        Hero {}
+
+        div{class: "container-fluid",
+
+            if let Some(subs) = &*submissions_by_category.read_unchecked(){
+                if subs.unsorted.is_empty() == false{
+                    LeaderboardTable{
+                        category: Category::None,
+                        elements: subs.unsorted.clone(),
+                        scoring: subs.scoring,
+                        scores: subs.scores.clone(),
+                        dragged_guid
+                    }
+                }
+                div{class: "row mt-4",
+                    div{class: "col",
+                        LeaderboardTable{
+                            category: Category::Poetry,
+                            elements: subs.poetry.clone(),
+                            scoring: subs.scoring,
+                            scores: subs.scores.clone(),
+                            dragged_guid
+                        }
+                    }
+                    div{class: "col",
+                        LeaderboardTable{
+                            category: Category::Fiction,
+                            elements: subs.fiction.clone(),
+                            scoring: subs.scoring,
+                            scores: subs.scores.clone(),
+                            dragged_guid
+                        }
+                    }
+                    div{class: "col",
+                        LeaderboardTable{
+                            category: Category::PersonalEssay,
+                            elements: subs.essay.clone(),
+                            scoring: subs.scoring,
+                            scores: subs.scores.clone(),
+                            dragged_guid
+                        }
+                    }
+                }
+            }
+            ClapChart{id: "clap_chart".to_string(), submissions_by_category}
+        }
+    }
+}
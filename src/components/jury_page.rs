@@ -0,0 +1,165 @@
+use crate::models::{JudgeScore, JuryCriterion, Submission};
+use crate::server_functions::{get_jury_ballot, submit_judge_scores};
+use dioxus::logger::tracing;
+use dioxus::prelude::*;
+
+/// Where judges score the entries of the current contest.
+#[component]
+pub fn JuryPage() -> Element {
+    let mut draft_token = use_signal(String::new);
+    let mut token = use_signal(String::new);
+
+    let ballot = use_resource(move || async move {
+        if token().is_empty() {
+            return Ok(None);
+        }
+        get_jury_ballot(token()).await.map(Some)
+    });
+
+    rsx! {
+        document::Title{"Jury"}
+        document::Meta{ name: "robots", content: "noindex" }
+        div{class: "container py-4",
+            h1{"Jury"}
+            form{class: "row g-2 my-3",
+                onsubmit: move |ev| {
+                    ev.prevent_default();
+                    token.set(draft_token());
+                },
+                div{class: "col-auto",
+                    input{
+                        class: "form-control",
+                        r#type: "password",
+                        placeholder: "Judge token",
+                        value: "{draft_token}",
+                        oninput: move |ev| draft_token.set(ev.value()),
+                    }
+                }
+                div{class: "col-auto",
+                    button{class: "btn btn-primary", r#type: "submit", "Log in"}
+                }
+            }
+
+            match &*ballot.read_unchecked() {
+                Some(Ok(Some(ballot))) => rsx! {
+                    p{
+                        {format!("Scoring {} as ", ballot.contest.title)}
+                        strong{{ballot.judge.clone()}}
+                        ". Other judges' scores stay hidden until the results are published."
+                    }
+                    if ballot.contest.jury_published_at.is_some(){
+                        div{class: "alert alert-info", "The results are published, scores can no longer be changed."}
+                    }
+                    for entry in ballot.entries.clone(){
+                        JuryScoreForm{
+                            key: "{entry.guid}",
+                            token,
+                            criteria: ballot.criteria.clone(),
+                            scores: ballot.scores.iter().filter(|score| score.guid == entry.guid).cloned().collect::<Vec<_>>(),
+                            entry,
+                        }
+                    }
+                },
+                Some(Err(err)) => {
+                    tracing::error!("{}", err);
+                    rsx! { div{class: "alert alert-danger", {err.to_string()}} }
+                }
+                _ => rsx! {},
+            }
+        }
+    }
+}
+
+#[component]
+fn JuryScoreForm(token: Signal<String>, criteria: Vec<JuryCriterion>, entry: Submission, scores: Vec<JudgeScore>) -> Element {
+    let mut values = use_signal(|| {
+        criteria
+            .iter()
+            .map(|criterion| {
+                let given = scores.iter().find(|score| score.criterion == criterion.name);
+                (
+                    given.map(|score| score.score.to_string()).unwrap_or_default(),
+                    given.map(|score| score.comment.clone()).unwrap_or_default(),
+                )
+            })
+            .collect::<Vec<_>>()
+    });
+    let mut message = use_signal(|| None::<String>);
+    let guid = entry.guid.clone();
+    let submitted_criteria = criteria.clone();
+
+    rsx! {
+        form{class: "card mb-3",
+            onsubmit: move |ev| {
+                let criteria = submitted_criteria.clone();
+                let guid = guid.clone();
+                async move {
+                    ev.prevent_default();
+                    let mut scores = Vec::new();
+                    for (criterion, (score, comment)) in criteria.iter().zip(values()) {
+                        if score.trim().is_empty() {
+                            continue;
+                        }
+                        match score.trim().parse::<i32>() {
+                            Ok(score) => scores.push((criterion.name.clone(), score, comment)),
+                            Err(_) => {
+                                message.set(Some(format!("{} must be a whole number", criterion.name)));
+                                return;
+                            }
+                        }
+                    }
+                    match submit_judge_scores(token(), guid, scores).await {
+                        Ok(()) => message.set(Some("Saved.".to_owned())),
+                        Err(err) => {
+                            tracing::error!("{}", err);
+                            message.set(Some(err.to_string()));
+                        }
+                    }
+                }
+            },
+            div{class: "card-body",
+                h5{class: "card-title",
+                    a{
+                        href: format!("https://medium.com/p/{}", entry.guid),
+                        target: "_blank",
+                        rel: "noopener noreferrer",
+                        {entry.title.clone()}
+                    }
+                }
+                h6{class: "card-subtitle mb-2 text-body-secondary",
+                    {format!("{} · {} · {} words", entry.realname, entry.category.name(), entry.word_count)}
+                }
+                for (i, criterion) in criteria.iter().cloned().enumerate(){
+                    div{class: "row g-2 mb-2",
+                        div{class: "col-md-3",
+                            div{class: "input-group",
+                                span{class: "input-group-text", {criterion.name.clone()}}
+                                input{
+                                    class: "form-control",
+                                    r#type: "number",
+                                    min: "0",
+                                    max: criterion.max.to_string(),
+                                    value: values.read()[i].0.clone(),
+                                    oninput: move |ev| values.write()[i].0 = ev.value(),
+                                }
+                                span{class: "input-group-text", {format!("/ {}", criterion.max)}}
+                            }
+                        }
+                        div{class: "col-md-9",
+                            input{
+                                class: "form-control",
+                                placeholder: "Comment",
+                                value: values.read()[i].1.clone(),
+                                oninput: move |ev| values.write()[i].1 = ev.value(),
+                            }
+                        }
+                    }
+                }
+                button{class: "btn btn-primary btn-sm", r#type: "submit", "Save"}
+                if let Some(message) = message(){
+                    span{class: "ms-2", {message}}
+                }
+            }
+        }
+    }
+}
@@ -1,10 +1,23 @@
+use crate::components::route::Route;
 use crate::server_functions::update_category;
 use dioxus::prelude::*;
 use dioxus::logger::tracing;
-use crate::models::{Category, Submission};
+use crate::models::{Category, ScoredSubmission, Scoring, Submission};
+use std::collections::HashMap;
 
+/// Entries are expected in rank order, ranks come from `scores`. Entries without a score, from
+/// older contests, are not ranked. The score column is only shown when the contest is not
+/// ranked by plain claps.
 #[component]
-pub fn LeaderboardTable(category: Category, elements: Vec<Submission>, dragged_guid: Signal<Option<String>>) -> Element {
+pub fn LeaderboardTable(
+    category: Category,
+    elements: Vec<Submission>,
+    scoring: Scoring,
+    scores: HashMap<String, ScoredSubmission>,
+    dragged_guid: Signal<Option<String>>,
+) -> Element {
+    let show_score = scoring != Scoring::Claps;
+
     rsx! {
             div{class: "h1 text-center",
                 ondragover: |ev| ev.prevent_default(),
@@ -34,13 +47,18 @@ pub fn LeaderboardTable(category: Category, elements: Vec<Submission>, dragged_g
                             th{"Rank"}
                             th{"Title"}
                             th{"Author"}
-                            th{"Claps " i{class: "fa-solid fa-arrow-down"}}
+                            if show_score{
+                                th{{scoring.name()} " " i{class: "fa-solid fa-arrow-down"}}
+                                th{"Claps"}
+                            } else {
+                                th{"Claps " i{class: "fa-solid fa-arrow-down"}}
+                            }
                             th{"Word count"}
                         }
                     }
                 tbody{
                     class: "table-group-divider",
-                    for (i, submission) in elements.iter().cloned().enumerate(){
+                    for submission in elements.iter().cloned(){
                         tr{
                             draggable: true,
                             ondragstart: move |ev| {
@@ -48,18 +66,43 @@ pub fn LeaderboardTable(category: Category, elements: Vec<Submission>, dragged_g
                                 tracing::info!("DragStart: {:?}", ev);
                                 tracing::info!("guid: {:?}", dragged_guid);
                             },
-                            th{
-                                {format!("{}.", i+1)}
+                            match scores.get(&submission.guid) {
+                                Some(scored) => rsx! {
+                                    th{
+                                        title: scored.tie_break.clone().unwrap_or_default(),
+                                        {format!("{}.", scored.rank)}
+                                        if scored.tie_break.is_some(){
+                                            sup{" *"}
+                                        }
+                                    }
+                                },
+                                None => rsx! { th{"–"} },
                             }
                             td{
                                 {submission.title.clone()}
                             }
                             td{
-                                a{
-                                    href: format!("https://medium.com/@{}", submission.username.clone()),
-                                    target: "_blank",
-                                    rel: "noopener noreferrer",
-                                    {format!("@{}", submission.username.clone())}
+                                if let Some(author_id) = submission.author_id.clone(){
+                                    Link{
+                                        to: Route::AuthorPage{id: author_id},
+                                        {format!("@{}", submission.username.clone())}
+                                    }
+                                } else {
+                                    a{
+                                        href: format!("https://medium.com/@{}", submission.username.clone()),
+                                        target: "_blank",
+                                        rel: "noopener noreferrer",
+                                        {format!("@{}", submission.username.clone())}
+                                    }
+                                }
+                            }
+                            if show_score{
+                                td{
+                                    if let Some(scored) = scores.get(&submission.guid){
+                                        {format!("{:.1}", scored.score)}
+                                        br{}
+                                        small{class: "text-body-secondary", {scored.breakdown.clone()}}
+                                    }
                                 }
                             }
                             td{
@@ -0,0 +1,120 @@
+use crate::models::{Category, InsertSubmission};
+use crate::server_functions::{add_submission, preview_submission};
+use dioxus::logger::tracing;
+use dioxus::prelude::*;
+
+#[component]
+pub fn ManualSubmissionForm(token: Signal<String>) -> Element {
+    let mut url_or_id = use_signal(String::new);
+    let mut category = use_signal(|| Category::None);
+    let mut preview = use_signal(|| None::<(InsertSubmission, bool)>);
+    let mut message = use_signal(|| None::<String>);
+
+    let categories = [
+        Category::None,
+        Category::Poetry,
+        Category::Fiction,
+        Category::PersonalEssay,
+    ];
+
+    rsx! {
+        form{class: "row g-2 my-3",
+            onsubmit: move |ev| async move {
+                ev.prevent_default();
+                preview.set(None);
+                match preview_submission(token(), url_or_id()).await {
+                    Ok(details) => {
+                        message.set(None);
+                        preview.set(Some(details));
+                    }
+                    Err(err) => {
+                        tracing::error!("{}", err);
+                        message.set(Some(err.to_string()));
+                    }
+                }
+            },
+            div{class: "col",
+                input{
+                    class: "form-control",
+                    placeholder: "Medium URL or post id",
+                    value: "{url_or_id}",
+                    oninput: move |ev| url_or_id.set(ev.value()),
+                }
+            }
+            div{class: "col-auto",
+                button{class: "btn btn-outline-primary", r#type: "submit", "Preview"}
+            }
+        }
+
+        if let Some(message) = message(){
+            div{class: "alert alert-info", {message}}
+        }
+
+        if let Some((details, already_present)) = preview(){
+            div{class: "card mb-3",
+                div{class: "row g-0",
+                    div{class: "col-md-3",
+                        if !details.img_id.is_empty(){
+                            img{
+                                class: "img-fluid rounded-start",
+                                src: format!("https://miro.medium.com/v2/resize:fit:400/{}", details.img_id),
+                            }
+                        }
+                    }
+                    div{class: "col-md-9",
+                        div{class: "card-body",
+                            h5{class: "card-title", {details.title.clone()}}
+                            p{class: "card-text",
+                                {format!("{} (@{}) · {} claps · {} words", details.realname, details.username, details.clap_count, details.word_count)}
+                            }
+                            if already_present{
+                                p{class: "text-warning", "This story is already on the leaderboard."}
+                            } else {
+                                div{class: "row g-2",
+                                    div{class: "col-auto",
+                                        select{
+                                            class: "form-select",
+                                            onchange: move |ev| {
+                                                if let Some(selected) = categories.iter().find(|c| c.name() == ev.value()) {
+                                                    category.set(*selected);
+                                                }
+                                            },
+                                            for c in categories{
+                                                option{value: c.name(), selected: c == category(), {c.name()}}
+                                            }
+                                        }
+                                    }
+                                    div{class: "col-auto",
+                                        button{
+                                            class: "btn btn-primary",
+                                            onclick: {
+                                                let guid = details.guid.clone();
+                                                move |_| {
+                                                    let guid = guid.clone();
+                                                    async move {
+                                                        match add_submission(token(), guid.clone(), category()).await {
+                                                            Ok(()) => {
+                                                                preview.set(None);
+                                                                url_or_id.set(String::new());
+                                                                message.set(Some(format!("Added {}.", guid)));
+                                                            }
+                                                            Err(err) => {
+                                                                tracing::error!("{}", err);
+                                                                message.set(Some(err.to_string()));
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            },
+                                            "Add to leaderboard"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
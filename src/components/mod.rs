@@ -1,6 +1,22 @@
+pub mod admin_page;
 pub mod app;
+pub mod author_page;
 pub mod clap_chart;
 pub mod config_info_table;
+pub mod contest_page;
+pub mod digest_subscription_form;
+pub mod embed_page;
 pub mod hero;
+pub mod home;
+pub mod jury_page;
 pub mod leaderboard_table;
+pub mod manual_submission_form;
 pub mod navbar;
+pub mod page_meta;
+pub mod readers_choice_page;
+pub mod route;
+pub mod scoring_selector;
+pub mod standings_table;
+pub mod statistics_page;
+pub mod submission_page;
+pub mod time_travel_page;
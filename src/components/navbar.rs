@@ -1,14 +1,39 @@
 use crate::components::app::FAVICON;
+use crate::components::route::Route;
+use crate::models::Category;
+use crate::server_functions::get_site_info;
 use dioxus::prelude::*;
 
+const BOOTSTRAP: Asset = asset!("/assets/styling/bootstrap.min.css");
+const BOOTSTRAP_JS: Asset = asset!("/assets/scripts/bootstrap.bundle.min.js");
+const FONTAWESOME: Asset = asset!("/assets/scripts/98b204fec6.js");
+const PLOTLY: Asset = asset!("/assets/scripts/plotly-2.14.0.min.js");
+const THEME_SWITCHER: Asset = asset!("/assets/scripts/theme-switcher.js");
+
 #[component]
 pub fn Navbar() -> Element {
+    let site_info = use_resource(get_site_info);
+    let (contest, voting) = match &*site_info.read_unchecked() {
+        Some(Ok(site_info)) => (site_info.contest.slug.clone(), site_info.voting),
+        _ => (String::new(), false),
+    };
+    let category_route = move |category: Category| Route::CategoryPage {
+        contest: contest.clone(),
+        category: category.slug().to_string(),
+    };
+
     rsx! {
+        document::Link{ rel: "stylesheet", href: BOOTSTRAP }
+        script { src: THEME_SWITCHER }
+        script { src: BOOTSTRAP_JS }
+        script { src: FONTAWESOME }
+        script { src: PLOTLY }
+
         header { class: "p-3 text-bg-dark",
             div { class: "container",
                 div { class: "d-flex flex-wrap align-items-center justify-content-center justify-content-lg-start",
-                    a {
-                        href: "/",
+                    Link {
+                        to: Route::Home {},
                         class: "d-flex align-items-center mb-2 mb-lg-0 text-white text-decoration-none",
                         img {
                             width: "40",
@@ -21,16 +46,24 @@ pub fn Navbar() -> Element {
                     }
                     ul { class: "nav col-lg-auto me-lg-auto mb-2 justify-content-center mb-md-0",
                         li {
-                            a { href: "#", class: "nav-link px-2 text-secondary", "Home" }
+                            Link { to: Route::Home {}, class: "nav-link px-2 text-secondary", "Home" }
+                        }
+                        li {
+                            Link { to: category_route(Category::Poetry), class: "nav-link px-2 text-white", "Poetry Competition" }
                         }
                         li {
-                            a { href: "#", class: "nav-link px-2 text-white", "Poetry Competition" }
+                            Link { to: category_route(Category::Fiction), class: "nav-link px-2 text-white", "Fiction Competition" }
                         }
                         li {
-                            a { href: "#", class: "nav-link px-2 text-white", "Fiction Competition" }
+                            Link { to: category_route(Category::PersonalEssay), class: "nav-link px-2 text-white", "Personal Essay Competition" }
+                        }
+                        if voting {
+                            li {
+                                Link { to: Route::ReadersChoicePage {}, class: "nav-link px-2 text-white", "Readers' Choice" }
+                            }
                         }
                         li {
-                            a { href: "#", class: "nav-link px-2 text-white", "Personal Essay Competition" }
+                            Link { to: Route::StatisticsPage {}, class: "nav-link px-2 text-white", "Statistics" }
                         }
                     }
                     div{
@@ -45,5 +78,7 @@ pub fn Navbar() -> Element {
                 }
             }
         }
+
+        Outlet::<Route> {}
     }
-}
+}
\ No newline at end of file
@@ -0,0 +1,70 @@
+use crate::models::Category;
+use crate::server_functions::get_site_info;
+use dioxus::prelude::*;
+
+const SITE_NAME: &str = "Medium Leaderboard";
+
+/// Title, description, canonical url, Open Graph and Twitter tags and optional JSON-LD of a page.
+/// The preview image is the standings card of `category`, or of the whole contest. Rendered on the
+/// server, so crawlers and link previews see it without JavaScript.
+#[component]
+pub fn PageMeta(
+    title: String,
+    description: String,
+    /// Path of the page, without the host.
+    path: String,
+    category: Option<Category>,
+    json_ld: Option<serde_json::Value>,
+) -> Element {
+    let site_info = use_server_future(get_site_info)?;
+    let Some(Ok(site_info)) = &*site_info.read_unchecked() else {
+        return rsx! { document::Title{ {title} } };
+    };
+    let url = format!("{}{}", site_info.public_url, path);
+    let image = format!("{}{}", site_info.public_url, site_info.contest.card_path(category));
+    let title = format!("{} · {}", title, SITE_NAME);
+    let json_ld = json_ld.map(|mut json_ld| {
+        absolutize(&mut json_ld, &site_info.public_url);
+        // A title containing `</script>` must not end the script element.
+        json_ld.to_string().replace("</", "<\\/")
+    });
+
+    rsx! {
+        document::Title{ {title.clone()} }
+        document::Meta{ name: "description", content: description.clone() }
+        document::Link{ rel: "canonical", href: url.clone() }
+        document::Meta{ property: "og:site_name", content: SITE_NAME }
+        document::Meta{ property: "og:type", content: "website" }
+        document::Meta{ property: "og:title", content: title.clone() }
+        document::Meta{ property: "og:description", content: description.clone() }
+        document::Meta{ property: "og:url", content: url }
+        document::Meta{ property: "og:image", content: image.clone() }
+        document::Meta{ property: "og:image:width", content: "1200" }
+        document::Meta{ property: "og:image:height", content: "630" }
+        document::Meta{ name: "twitter:card", content: "summary_large_image" }
+        document::Meta{ name: "twitter:title", content: title }
+        document::Meta{ name: "twitter:description", content: description }
+        document::Meta{ name: "twitter:image", content: image }
+        if let Some(json_ld) = json_ld{
+            document::Script{ r#type: "application/ld+json", {json_ld} }
+        }
+    }
+}
+
+/// JSON-LD needs full urls, pages pass paths in `url` and `@id`.
+fn absolutize(value: &mut serde_json::Value, public_url: &str) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                match value {
+                    serde_json::Value::String(path) if (key == "url" || key == "@id") && path.starts_with('/') => {
+                        *path = format!("{}{}", public_url, path);
+                    }
+                    _ => absolutize(value, public_url),
+                }
+            }
+        }
+        serde_json::Value::Array(values) => values.iter_mut().for_each(|value| absolutize(value, public_url)),
+        _ => {}
+    }
+}
@@ -0,0 +1,172 @@
+use crate::components::page_meta::PageMeta;
+use crate::components::route::Route;
+use crate::models::{Category, ReadersChoice};
+use crate::server_functions::{get_readers_choice, reader_logout, request_reader_login, set_reader_vote};
+use dioxus::logger::tracing;
+use dioxus::prelude::*;
+
+/// Ranking by reader votes, separate from the clap leaderboard. Logged-in readers vote here.
+#[component]
+pub fn ReadersChoicePage() -> Element {
+    let mut board = use_resource(get_readers_choice);
+    let mut message = use_signal(|| None::<String>);
+
+    let choice: ReadersChoice = match &*board.read_unchecked() {
+        None => return rsx! { div{class: "container py-4", "Loading..."} },
+        Some(Err(err)) => {
+            tracing::error!("{}", err);
+            return rsx! { div{class: "container py-4", "The readers' choice is not available."} };
+        }
+        Some(Ok(choice)) => choice.clone(),
+    };
+    let can_vote = choice.open && choice.reader.is_some();
+
+    rsx! {
+        PageMeta{
+            title: format!("Readers' choice – {}", choice.contest.title),
+            description: format!(
+                "The entries of the {} contest ranked by reader votes, {} per reader and category.",
+                choice.contest.title, choice.votes_per_category
+            ),
+            path: "/readers-choice".to_string(),
+        }
+        div{class: "container py-4",
+            h1{"Readers' choice"}
+            p{
+                {format!(
+                    "Every reader has {} votes per category. Log in with your email to vote, the board only counts verified votes.",
+                    choice.votes_per_category
+                )}
+            }
+
+            if choice.open{
+                match choice.reader.clone() {
+                    Some(email) => rsx! {
+                        p{
+                            {format!("Logged in as {}.", email)}
+                            button{
+                                class: "btn btn-sm btn-link",
+                                onclick: move |_| async move {
+                                    if let Err(err) = reader_logout().await {
+                                        tracing::error!("{}", err);
+                                    }
+                                    board.restart();
+                                },
+                                "Log out"
+                            }
+                        }
+                    },
+                    None => rsx! { ReaderLoginForm{} },
+                }
+            } else {
+                div{class: "alert alert-info", "Voting is closed."}
+            }
+
+            if let Some(text) = message(){
+                div{class: "alert alert-warning", {text}}
+            }
+
+            for category in Category::ALL.into_iter().filter(|category| *category != Category::None){
+                h4{class: "mt-4", {category.name()}}
+                table{class: "table table-hover table-striped table-bordered",
+                    thead{
+                        tr{
+                            th{"Rank"}
+                            th{"Title"}
+                            th{"Author"}
+                            th{"Votes"}
+                            if can_vote{
+                                th{{
+                                    let used = choice.entries.iter()
+                                        .filter(|entry| entry.submission.category == category && choice.my_votes.contains(&entry.submission.guid))
+                                        .count();
+                                    format!("Your votes ({} of {})", used, choice.votes_per_category)
+                                }}
+                            }
+                        }
+                    }
+                    tbody{
+                        class: "table-group-divider",
+                        for (i, entry) in choice.entries.iter().filter(|entry| entry.submission.category == category).cloned().enumerate(){
+                            tr{
+                                th{{format!("{}.", i + 1)}}
+                                td{
+                                    Link{
+                                        to: Route::SubmissionPage{guid: entry.submission.guid.clone()},
+                                        {entry.submission.title.clone()}
+                                    }
+                                }
+                                td{{entry.submission.realname.clone()}}
+                                td{{entry.votes.to_string()}}
+                                if can_vote{
+                                    td{
+                                        {
+                                            let voted = choice.my_votes.contains(&entry.submission.guid);
+                                            let guid = entry.submission.guid.clone();
+                                            rsx! {
+                                                button{
+                                                    class: if voted { "btn btn-sm btn-primary" } else { "btn btn-sm btn-outline-primary" },
+                                                    onclick: move |_| {
+                                                        let guid = guid.clone();
+                                                        async move {
+                                                            match set_reader_vote(guid, !voted).await {
+                                                                Ok(()) => message.set(None),
+                                                                Err(err) => {
+                                                                    tracing::error!("{}", err);
+                                                                    message.set(Some(err.to_string()));
+                                                                }
+                                                            }
+                                                            board.restart();
+                                                        }
+                                                    },
+                                                    i{class: "fa-solid fa-heart"}
+                                                    if voted { " Voted" } else { " Vote" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ReaderLoginForm() -> Element {
+    let mut email = use_signal(String::new);
+    let mut message = use_signal(|| None::<String>);
+
+    rsx! {
+        form{class: "row g-2 my-3",
+            onsubmit: move |ev| async move {
+                ev.prevent_default();
+                match request_reader_login(email()).await {
+                    Ok(()) => message.set(Some("Check your inbox for the login link.".to_string())),
+                    Err(err) => {
+                        tracing::error!("{}", err);
+                        message.set(Some(err.to_string()));
+                    }
+                }
+            },
+            div{class: "col-auto",
+                input{
+                    class: "form-control",
+                    r#type: "email",
+                    placeholder: "you@example.com",
+                    value: "{email}",
+                    oninput: move |ev| email.set(ev.value()),
+                }
+            }
+            div{class: "col-auto",
+                button{class: "btn btn-primary", r#type: "submit", "Send login link"}
+            }
+        }
+        if let Some(message) = message(){
+            div{class: "alert alert-info", {message}}
+        }
+    }
+}
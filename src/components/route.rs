@@ -0,0 +1,41 @@
+use crate::components::admin_page::AdminPage;
+use crate::components::author_page::AuthorPage;
+use crate::components::contest_page::{CategoryPage, ContestPage};
+use crate::components::embed_page::EmbedPage;
+use crate::components::home::Home;
+use crate::components::jury_page::JuryPage;
+use crate::components::navbar::Navbar;
+use crate::components::readers_choice_page::ReadersChoicePage;
+use crate::components::statistics_page::StatisticsPage;
+use crate::components::submission_page::SubmissionPage;
+use crate::components::time_travel_page::TimeTravelPage;
+use dioxus::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Routable)]
+#[rustfmt::skip]
+pub enum Route {
+    #[layout(Navbar)]
+        #[route("/")]
+        Home {},
+        #[route("/contest/:contest")]
+        ContestPage { contest: String },
+        #[route("/contest/:contest/:category")]
+        CategoryPage { contest: String, category: String },
+        #[route("/history/:contest")]
+        TimeTravelPage { contest: String },
+        #[route("/submission/:guid")]
+        SubmissionPage { guid: String },
+        #[route("/author/:id")]
+        AuthorPage { id: String },
+        #[route("/readers-choice")]
+        ReadersChoicePage {},
+        #[route("/statistics")]
+        StatisticsPage {},
+        #[route("/admin")]
+        AdminPage {},
+        #[route("/jury")]
+        JuryPage {},
+    #[end_layout]
+    #[route("/embed/:contest/:category?:theme&:accent")]
+    EmbedPage { contest: String, category: String, theme: String, accent: String },
+}
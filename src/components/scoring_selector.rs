@@ -0,0 +1,114 @@
+use crate::models::Scoring;
+use crate::server_functions::{get_site_info, set_contest_scoring};
+use dioxus::logger::tracing;
+use dioxus::prelude::*;
+
+/// Picks the scoring strategy of the current contest. A `scoring` table in the settings
+/// overrides the choice on the next restart.
+#[component]
+pub fn ScoringSelector(token: Signal<String>) -> Element {
+    let mut site_info = use_resource(get_site_info);
+    let mut scoring = use_signal(Scoring::default);
+    let mut clap_weight = use_signal(|| 0.5);
+    let mut judge_weight = use_signal(|| 0.5);
+    let mut message = use_signal(|| None::<String>);
+
+    let contest = match &*site_info.read_unchecked() {
+        Some(Ok(site_info)) => Some(site_info.contest.clone()),
+        _ => None,
+    };
+
+    use_effect(move || {
+        if let Some(Ok(site_info)) = &*site_info.read() {
+            let current = site_info.contest.scoring();
+            if let Scoring::Blend { clap_weight: claps, judge_weight: jury } = current {
+                clap_weight.set(claps);
+                judge_weight.set(jury);
+            }
+            scoring.set(current);
+        }
+    });
+
+    let Some(contest) = contest else {
+        return rsx! {};
+    };
+
+    rsx! {
+        p{
+            {format!("{} is ranked by ", contest.title)}
+            strong{{contest.scoring().name()}}
+            "."
+        }
+        form{class: "row g-2 my-3",
+            onsubmit: move |ev| {
+                let slug = contest.slug.clone();
+                async move {
+                    ev.prevent_default();
+                    let selected = match scoring() {
+                        Scoring::Blend { .. } => Scoring::Blend {
+                            clap_weight: clap_weight(),
+                            judge_weight: judge_weight(),
+                        },
+                        selected => selected,
+                    };
+                    match set_contest_scoring(token(), slug, selected).await {
+                        Ok(()) => {
+                            message.set(None);
+                            site_info.restart();
+                        }
+                        Err(err) => {
+                            tracing::error!("{}", err);
+                            message.set(Some(err.to_string()));
+                        }
+                    }
+                }
+            },
+            div{class: "col-auto",
+                select{
+                    class: "form-select",
+                    onchange: move |ev| {
+                        if let Some(selected) = Scoring::KINDS.iter().find(|kind| kind.name() == ev.value()) {
+                            scoring.set(*selected);
+                        }
+                    },
+                    for kind in Scoring::KINDS{
+                        option{
+                            value: kind.name(),
+                            selected: kind.name() == scoring().name(),
+                            {kind.name()}
+                        }
+                    }
+                }
+            }
+            if let Scoring::Blend { .. } = scoring(){
+                div{class: "col-auto input-group w-auto",
+                    span{class: "input-group-text", "Claps"}
+                    input{
+                        class: "form-control",
+                        r#type: "number",
+                        step: "0.05",
+                        min: "0",
+                        value: "{clap_weight}",
+                        oninput: move |ev| clap_weight.set(ev.value().parse().unwrap_or_default()),
+                    }
+                    span{class: "input-group-text", "Jury"}
+                    input{
+                        class: "form-control",
+                        r#type: "number",
+                        step: "0.05",
+                        min: "0",
+                        value: "{judge_weight}",
+                        oninput: move |ev| judge_weight.set(ev.value().parse().unwrap_or_default()),
+                    }
+                }
+            }
+            div{class: "col-auto",
+                button{class: "btn btn-primary", r#type: "submit", "Apply"}
+            }
+        }
+
+        if let Some(message) = message(){
+            div{class: "alert alert-info", {message}}
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::components::route::Route;
+use crate::models::{JuryResult, ScoredSubmission};
+use dioxus::prelude::*;
+use std::collections::HashMap;
+
+/// Read-only ranking of `entries`, which are expected to be ordered by rank. The jury column
+/// is shown once `jury` has results.
+#[component]
+pub fn StandingsTable(entries: Vec<ScoredSubmission>, #[props(default)] jury: HashMap<String, JuryResult>) -> Element {
+    let show_jury = !jury.is_empty();
+
+    rsx! {
+        table{class: "table table-hover table-striped table-bordered",
+            thead{
+                tr{
+                    th{"Rank"}
+                    th{"Title"}
+                    th{"Author"}
+                    th{"Score"}
+                    th{"Claps"}
+                    if show_jury{
+                        th{"Jury"}
+                    }
+                    th{"Word count"}
+                }
+            }
+            tbody{
+                class: "table-group-divider",
+                for entry in entries{
+                    tr{
+                        th{
+                            title: entry.tie_break.clone().unwrap_or_default(),
+                            {format!("{}.", entry.rank)}
+                            if entry.tie_break.is_some(){
+                                sup{" *"}
+                            }
+                        }
+                        td{
+                            Link{
+                                to: Route::SubmissionPage{guid: entry.submission.guid.clone()},
+                                {entry.submission.title.clone()}
+                            }
+                        }
+                        td{
+                            if let Some(author_id) = entry.submission.author_id.clone(){
+                                Link{
+                                    to: Route::AuthorPage{id: author_id},
+                                    {entry.submission.realname.clone()}
+                                }
+                            } else {
+                                {entry.submission.realname.clone()}
+                            }
+                        }
+                        td{
+                            {format!("{:.1}", entry.score)}
+                            br{}
+                            small{class: "text-body-secondary", {entry.breakdown.clone()}}
+                        }
+                        td{{entry.submission.clap_count.to_string()}}
+                        if show_jury{
+                            td{
+                                if let Some(result) = jury.get(&entry.submission.guid){
+                                    {format!("{:.1}", result.score)}
+                                    br{}
+                                    small{class: "text-body-secondary",
+                                        {result.criteria.iter().map(|(name, score)| format!("{} {:.1}", name, score)).collect::<Vec<_>>().join(" · ")}
+                                    }
+                                }
+                            }
+                        }
+                        td{{entry.submission.word_count.to_string()}}
+                    }
+                }
+            }
+        }
+    }
+}
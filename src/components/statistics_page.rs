@@ -0,0 +1,223 @@
+use crate::components::page_meta::PageMeta;
+use crate::components::route::Route;
+use crate::models::{AuthorRanking, AuthorTotals, Category, ContestClapActivity, ContestParticipation};
+use crate::server_functions::*;
+use dioxus::logger::tracing;
+use dioxus::prelude::*;
+use plotly::color::Rgb;
+use plotly::common::Mode;
+use plotly::configuration::*;
+use plotly::layout::*;
+use plotly::{Bar, Plot, Scatter};
+
+const HALL_OF_FAME_SIZE: i64 = 10;
+
+#[component]
+pub fn StatisticsPage() -> Element {
+    let by_claps = use_resource(|| get_top_authors(AuthorRanking::TotalClaps, HALL_OF_FAME_SIZE));
+    let by_entries = use_resource(|| get_top_authors(AuthorRanking::Entries, HALL_OF_FAME_SIZE));
+    let wins = use_resource(get_category_wins);
+    let participation = use_resource(get_contest_participation);
+    let activity = use_resource(get_contest_clap_activity);
+
+    let participation = use_memo(move || match &*participation.read_unchecked() {
+        Some(Ok(participation)) => Some(participation.clone()),
+        Some(Err(err)) => {
+            tracing::error!("{}", err);
+            None
+        }
+        None => None,
+    });
+    let activity = use_memo(move || match &*activity.read_unchecked() {
+        Some(Ok(activity)) => Some(activity.clone()),
+        Some(Err(err)) => {
+            tracing::error!("{}", err);
+            None
+        }
+        None => None,
+    });
+
+    rsx! {
+        PageMeta{
+            title: "Statistics".to_string(),
+            description: "Hall of fame, category wins, participation and clap activity across all contests.".to_string(),
+            path: "/statistics".to_string(),
+        }
+        div{class: "container py-4",
+            h1{"Hall of fame"}
+            div{class: "row mt-4",
+                div{class: "col",
+                    h4{"Most claps"}
+                    AuthorTotalsTable{totals: (*by_claps.read_unchecked()).clone().and_then(Result::ok)}
+                }
+                div{class: "col",
+                    h4{"Most entries"}
+                    AuthorTotalsTable{totals: (*by_entries.read_unchecked()).clone().and_then(Result::ok)}
+                }
+                div{class: "col",
+                    h4{"Category wins"}
+                    table{class: "table table-hover table-striped table-sm",
+                        thead{
+                            tr{
+                                th{"Author"}
+                                th{"Category"}
+                                th{"Wins"}
+                            }
+                        }
+                        tbody{
+                            if let Some(Ok(wins)) = &*wins.read_unchecked(){
+                                for win in wins.iter().cloned(){
+                                    tr{
+                                        td{
+                                            Link{
+                                                to: Route::AuthorPage{id: win.author_id},
+                                                {win.name}
+                                            }
+                                        }
+                                        td{{win.category.name()}}
+                                        td{{win.wins.to_string()}}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            h1{class: "mt-5", "Participation"}
+            ParticipationChart{id: "participation_chart".to_string(), participation}
+            ClapActivityChart{id: "clap_activity_chart".to_string(), activity}
+        }
+    }
+}
+
+#[component]
+fn AuthorTotalsTable(totals: Option<Vec<AuthorTotals>>) -> Element {
+    rsx! {
+        table{class: "table table-hover table-striped table-sm",
+            thead{
+                tr{
+                    th{"Rank"}
+                    th{"Author"}
+                    th{"Claps"}
+                    th{"Entries"}
+                    th{"Contests"}
+                }
+            }
+            tbody{
+                for (i, author) in totals.unwrap_or_default().into_iter().enumerate(){
+                    tr{
+                        th{{format!("{}.", i+1)}}
+                        td{
+                            Link{
+                                to: Route::AuthorPage{id: author.author_id},
+                                {author.name}
+                            }
+                        }
+                        td{{author.total_claps.to_string()}}
+                        td{{author.entries.to_string()}}
+                        td{{author.contests.to_string()}}
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn dark_layout(title: &str) -> Layout {
+    Layout::new()
+        .title(title)
+        .paper_background_color(Rgb::new(20, 22, 26))
+        .plot_background_color(Rgb::new(20, 22, 26))
+}
+
+#[component]
+fn ParticipationChart(id: String, participation: Memo<Option<Vec<ContestParticipation>>>) -> Element {
+    let plot_id = id.clone();
+
+    use_effect(move || {
+        if let Some(participation) = &*participation.read_unchecked() {
+            let mut contests: Vec<String> = Vec::new();
+            for row in participation {
+                if !contests.contains(&row.title) {
+                    contests.push(row.title.clone());
+                }
+            }
+
+            let mut plot = Plot::new();
+            for category in [Category::Poetry, Category::Fiction, Category::PersonalEssay] {
+                let entries = contests
+                    .iter()
+                    .map(|contest| {
+                        participation
+                            .iter()
+                            .find(|row| &row.title == contest && row.category == category)
+                            .map(|row| row.entries)
+                            .unwrap_or_default()
+                    })
+                    .collect();
+                plot.add_trace(Bar::new(contests.clone(), entries).name(category.name()));
+            }
+
+            plot.set_layout(dark_layout("Entries per contest").bar_mode(BarMode::Group));
+            plot.set_configuration(Configuration::default().display_mode_bar(DisplayModeBar::False));
+
+            let value = plot_id.clone();
+            spawn(async move {
+                plotly::bindings::new_plot(&value, &plot).await
+            });
+        }
+    });
+
+    rsx! {
+        div {class: "box mx-6",
+            div {
+                id: id,
+            }
+        }
+    }
+}
+
+#[component]
+fn ClapActivityChart(id: String, activity: Memo<Option<Vec<ContestClapActivity>>>) -> Element {
+    let plot_id = id.clone();
+
+    use_effect(move || {
+        if let Some(activity) = &*activity.read_unchecked() {
+            let mut plot = Plot::new();
+
+            let mut contest_ids: Vec<i32> = activity.iter().map(|row| row.contest_id).collect();
+            contest_ids.dedup();
+            for contest_id in contest_ids {
+                let rows: Vec<&ContestClapActivity> = activity
+                    .iter()
+                    .filter(|row| row.contest_id == contest_id)
+                    .collect();
+                plot.add_trace(
+                    Scatter::new(
+                        rows.iter().map(|row| row.day.to_string()).collect(),
+                        rows.iter().map(|row| row.claps_gained).collect(),
+                    )
+                    .mode(Mode::LinesMarkers)
+                    .name(&rows[0].title),
+                );
+            }
+
+            plot.set_layout(dark_layout("Claps gained per day"));
+            plot.set_configuration(Configuration::default().display_mode_bar(DisplayModeBar::False));
+
+            let value = plot_id.clone();
+            spawn(async move {
+                plotly::bindings::new_plot(&value, &plot).await
+            });
+        }
+    });
+
+    rsx! {
+        div {class: "box mx-6",
+            div {
+                id: id,
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use crate::components::page_meta::PageMeta;
+use crate::components::route::Route;
+use crate::server_functions::{get_judge_scores, get_submission};
+use dioxus::logger::tracing;
+use dioxus::prelude::*;
+
+#[component]
+pub fn SubmissionPage(guid: ReadOnlySignal<String>) -> Element {
+    let submission = use_server_future(move || get_submission(guid()))?;
+    let judge_scores = use_server_future(move || get_judge_scores(guid()))?;
+    let judge_scores = match &*judge_scores.read_unchecked() {
+        Some(Ok(scores)) => scores.clone(),
+        Some(Err(err)) => {
+            tracing::error!("{}", err);
+            Vec::new()
+        }
+        None => Vec::new(),
+    };
+    let mut judges: Vec<String> = judge_scores.iter().map(|score| score.judge.clone()).collect();
+    judges.dedup();
+
+    let (contest, submission) = match &*submission.read_unchecked() {
+        None => return rsx! { div{class: "container py-4", "Loading..."} },
+        Some(Err(err)) => {
+            tracing::error!("{}", err);
+            return rsx! { div{class: "container py-4", "Submission not found."} };
+        }
+        Some(Ok((contest, submission))) => (contest.clone(), submission.clone()),
+    };
+    let medium_url = format!("https://medium.com/p/{}", submission.guid);
+
+    let json_ld = serde_json::json!({
+        "@context": "https://schema.org",
+        "@type": "CreativeWork",
+        "headline": submission.title,
+        "url": format!("/submission/{}", submission.guid),
+        "sameAs": medium_url,
+        "wordCount": submission.word_count,
+        "datePublished": chrono::DateTime::from_timestamp_millis(submission.latest_published_at)
+            .map(|published_at| published_at.to_rfc3339()),
+        "genre": submission.category.name(),
+        "author": {
+            "@type": "Person",
+            "name": submission.realname,
+            "url": format!("https://medium.com/@{}", submission.username),
+        },
+        "interactionStatistic": {
+            "@type": "InteractionCounter",
+            "interactionType": "https://schema.org/LikeAction",
+            "userInteractionCount": submission.clap_count,
+        },
+    });
+
+    rsx! {
+        PageMeta{
+            title: submission.title.clone(),
+            description: format!(
+                "{} by {}, a {} entry in the {} contest with {} claps.",
+                submission.title, submission.realname, submission.category.name(), contest.title, submission.clap_count
+            ),
+            path: format!("/submission/{}", submission.guid),
+            category: submission.category,
+            json_ld,
+        }
+        div{class: "container py-4",
+            h1{{submission.title.clone()}}
+            p{
+                "by "
+                if let Some(author_id) = submission.author_id.clone(){
+                    Link{to: Route::AuthorPage{id: author_id}, {submission.realname.clone()}}
+                } else {
+                    {submission.realname.clone()}
+                }
+                " in "
+                Link{
+                    to: Route::CategoryPage{contest: contest.slug.clone(), category: submission.category.slug().to_string()},
+                    {format!("{} – {}", contest.title, submission.category.name())}
+                }
+            }
+            p{{format!("{} claps · {} words", submission.clap_count, submission.word_count)}}
+            a{
+                class: "btn btn-outline-primary",
+                href: medium_url,
+                target: "_blank",
+                rel: "noopener noreferrer",
+                "Read on Medium"
+            }
+
+            if !judges.is_empty(){
+                h4{class: "mt-4", "Jury"}
+                for judge in judges{
+                    h6{class: "mt-3", {judge.clone()}}
+                    ul{
+                        for score in judge_scores.iter().filter(|score| score.judge == judge){
+                            li{
+                                strong{{format!("{}: {}", score.criterion, score.score)}}
+                                if !score.comment.is_empty(){
+                                    {format!(" – {}", score.comment)}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::components::page_meta::PageMeta;
+use crate::components::route::Route;
+use crate::components::standings_table::StandingsTable;
+use crate::models::{Category, ScoredSubmission, Submission};
+use crate::server_functions::{get_contest_standings, get_time_travel};
+use dioxus::logger::tracing;
+use dioxus::prelude::*;
+use plotly::color::Rgb;
+use plotly::common::Orientation;
+use plotly::configuration::*;
+use plotly::layout::*;
+use plotly::traces::bar::*;
+use plotly::Plot;
+
+/// Entries shown in the bar chart.
+const CHART_LENGTH: usize = 10;
+/// Pause between two steps of the playback.
+const PLAYBACK_DELAY_MS: u32 = 400;
+
+/// The standings of a contest at any moment of its duration, with a playback of the race.
+#[component]
+pub fn TimeTravelPage(contest: ReadOnlySignal<String>) -> Element {
+    let standings = use_server_future(move || get_contest_standings(contest()))?;
+    // Every frame is fetched once, the slider and the playback only pick one.
+    let race = use_server_future(move || get_time_travel(contest()))?;
+    let last_step = use_memo(move || match &*race.read() {
+        Some(Ok(race)) => race.frames.len().saturating_sub(1),
+        _ => 0,
+    });
+    let mut step = use_signal(|| *last_step.peek());
+    let mut playing = use_signal(|| false);
+    let mut category = use_signal(|| Category::Poetry);
+
+    // The entries of the category at the current step, rebuilt from the frame and the submissions.
+    let entries = use_memo(move || {
+        let Some(Ok(race)) = &*race.read() else {
+            return Vec::new();
+        };
+        let Some(frame) = race.frames.get(step()) else {
+            return Vec::new();
+        };
+        frame
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let submission = race.submissions.iter().find(|submission| submission.guid == entry.guid)?;
+                (submission.category == category()).then(|| ScoredSubmission {
+                    submission: Submission {
+                        clap_count: entry.clap_count,
+                        ..submission.clone()
+                    },
+                    score: entry.score,
+                    breakdown: entry.breakdown.clone(),
+                    rank: entry.rank,
+                    tie_break: entry.tie_break.clone(),
+                })
+            })
+            .collect::<Vec<_>>()
+    });
+
+    use_effect(move || {
+        if let Some(Ok(_)) = &*race.read() {
+            let top: Vec<ScoredSubmission> = entries().into_iter().take(CHART_LENGTH).collect();
+
+            let mut plot = Plot::new();
+            // Plotly draws the first bar at the bottom, so the leader goes last.
+            plot.add_trace(
+                Bar::new(
+                    top.iter().rev().map(|entry| entry.score).collect(),
+                    top.iter().rev().map(|entry| format!("{}. {}", entry.rank, entry.submission.title)).collect(),
+                )
+                .orientation(Orientation::Horizontal),
+            );
+            plot.set_layout(
+                Layout::new()
+                    .paper_background_color(Rgb::new(20, 22, 26))
+                    .plot_background_color(Rgb::new(20, 22, 26))
+                    .margin(Margin::new().left(300)),
+            );
+            plot.set_configuration(Configuration::default().display_mode_bar(DisplayModeBar::False));
+
+            spawn(async move { plotly::bindings::react("time-travel-chart", &plot).await });
+        }
+    });
+
+    let play = move |_| async move {
+        if playing() {
+            playing.set(false);
+            return;
+        }
+        if step() >= last_step() {
+            step.set(0);
+        }
+        playing.set(true);
+        while playing() && step() < last_step() {
+            let pause = document::eval(&format!(
+                "await new Promise(resolve => setTimeout(resolve, {})); return true;",
+                PLAYBACK_DELAY_MS
+            ));
+            if let Err(err) = pause.await {
+                tracing::error!("{:?}", err);
+                break;
+            }
+            if playing() {
+                step += 1;
+            }
+        }
+        playing.set(false);
+    };
+
+    let (details, moment) = match (&*standings.read_unchecked(), &*race.read_unchecked()) {
+        (Some(Ok((details, _))), Some(Ok(race))) => (
+            details.clone(),
+            race.frames.get(step()).map(|frame| frame.at),
+        ),
+        (Some(Err(err)), _) | (_, Some(Err(err))) => {
+            tracing::error!("{}", err);
+            return rsx! { div{class: "container py-4", "Contest not found."} };
+        }
+        _ => return rsx! { div{class: "container py-4", "Loading..."} },
+    };
+    let entries = entries();
+
+    rsx! {
+        PageMeta{
+            title: format!("Time travel – {}", details.title),
+            description: format!("The standings of the {} contest at any moment since it started.", details.title),
+            path: format!("/history/{}", details.slug),
+        }
+        div{class: "container py-4",
+            h1{"Time travel"}
+            Link{to: Route::ContestPage{contest: details.slug.clone()}, {details.title.clone()}}
+
+            div{class: "d-flex align-items-center gap-3 mt-4",
+                button{
+                    class: "btn btn-primary",
+                    onclick: play,
+                    if playing(){ "Pause" } else { "Play" }
+                }
+                input{
+                    class: "form-range flex-grow-1",
+                    r#type: "range",
+                    min: "0",
+                    max: last_step().to_string(),
+                    value: step().to_string(),
+                    oninput: move |event| {
+                        playing.set(false);
+                        if let Ok(value) = event.value().parse::<usize>() {
+                            step.set(value.min(last_step()));
+                        }
+                    },
+                }
+                if let Some(moment) = moment{
+                    span{class: "text-nowrap", {moment.format("%Y-%m-%d %H:%M").to_string()}}
+                }
+            }
+
+            ul{class: "nav nav-tabs mt-4",
+                for tab in Category::ALL.into_iter().filter(|category| *category != Category::None){
+                    li{class: "nav-item",
+                        button{
+                            class: if category() == tab { "nav-link active" } else { "nav-link" },
+                            onclick: move |_| category.set(tab),
+                            {tab.name()}
+                        }
+                    }
+                }
+            }
+
+            div{class: "box mt-3",
+                div{id: "time-travel-chart"}
+            }
+
+            if entries.is_empty(){
+                p{class: "mt-3", "No entries yet."}
+            } else {
+                div{class: "mt-3",
+                    StandingsTable{entries}
+                }
+            }
+        }
+    }
+}
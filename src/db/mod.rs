@@ -9,9 +9,121 @@ pub mod submissions {
     }
 }
 
+pub mod api_responses {
+    pub mod dsl {
+        pub use crate::schema::api_responses::dsl::api_responses;
+        pub use crate::schema::api_responses::*;
+    }
+}
+
+pub mod backfill_progress {
+    pub mod dsl {
+        pub use crate::schema::backfill_progress::dsl::backfill_progress;
+        pub use crate::schema::backfill_progress::*;
+    }
+}
+
 pub mod clap_history {
     pub mod dsl {
         pub use crate::schema::clap_history::dsl::clap_history;
         pub use crate::schema::clap_history::*;
     }
 }
+
+pub mod authors {
+    pub mod dsl {
+        pub use crate::schema::authors::dsl::authors;
+        pub use crate::schema::authors::*;
+    }
+}
+
+pub mod contests {
+    pub mod dsl {
+        pub use crate::schema::contests::dsl::contests;
+        pub use crate::schema::contests::*;
+    }
+}
+
+pub mod email_subscriptions {
+    pub mod dsl {
+        pub use crate::schema::email_subscriptions::dsl::email_subscriptions;
+        pub use crate::schema::email_subscriptions::*;
+    }
+}
+
+pub mod ingest_errors {
+    pub mod dsl {
+        pub use crate::schema::ingest_errors::dsl::ingest_errors;
+        pub use crate::schema::ingest_errors::*;
+    }
+}
+
+pub mod ingest_items {
+    pub mod dsl {
+        pub use crate::schema::ingest_items::dsl::ingest_items;
+        pub use crate::schema::ingest_items::*;
+    }
+}
+
+pub mod job_leases {
+    pub mod dsl {
+        pub use crate::schema::job_leases::dsl::job_leases;
+        pub use crate::schema::job_leases::*;
+    }
+}
+
+pub mod job_runs {
+    pub mod dsl {
+        pub use crate::schema::job_runs::dsl::job_runs;
+        pub use crate::schema::job_runs::*;
+    }
+}
+
+pub mod job_schedules {
+    pub mod dsl {
+        pub use crate::schema::job_schedules::dsl::job_schedules;
+        pub use crate::schema::job_schedules::*;
+    }
+}
+
+pub mod judge_scores {
+    pub mod dsl {
+        pub use crate::schema::judge_scores::dsl::judge_scores;
+        pub use crate::schema::judge_scores::*;
+    }
+}
+
+pub mod notification_deliveries {
+    pub mod dsl {
+        pub use crate::schema::notification_deliveries::dsl::notification_deliveries;
+        pub use crate::schema::notification_deliveries::*;
+    }
+}
+
+pub mod readers {
+    pub mod dsl {
+        pub use crate::schema::readers::dsl::readers;
+        pub use crate::schema::readers::*;
+    }
+}
+
+pub mod login_requests {
+    pub mod dsl {
+        pub use crate::schema::login_requests::dsl::login_requests;
+        pub use crate::schema::login_requests::*;
+    }
+}
+
+pub mod subscription_requests {
+    pub mod dsl {
+        pub use crate::schema::subscription_requests::dsl::subscription_requests;
+        pub use crate::schema::subscription_requests::*;
+    }
+}
+
+pub mod votes {
+    pub mod dsl {
+        pub use crate::schema::votes::dsl::votes;
+        pub use crate::schema::votes::*;
+    }
+}
@@ -5,32 +5,90 @@ use std::env;
 
 #[cfg(feature = "server")]
 use {
-    axum::Router,
+    axum::{middleware, Router},
     dioxus::prelude::{DioxusRouterExt, ServeConfigBuilder},
     dioxus_cli_config::fullstack_address_or_localhost,
     dotenvy::dotenv,
-    medium_leaderboard::{db::*, server, ContextProviders},
+    medium_leaderboard::{
+        db::*,
+        server::{
+            self, cards, email, embed, feeds, health, http::MediumClient, settings::Settings, sitemap,
+            telemetry, voting, ServerState,
+        },
+        ContextProviders,
+    },
+    std::sync::Arc,
 };
 
 #[cfg(feature = "server")]
 fn standalone_setup(connection_string: &str) -> anyhow::Result<Router> {
-    let pool = match init_db_connection(&connection_string) {
-        Ok(pool) => pool,
+    let settings = match Settings::load() {
+        Ok(settings) => Arc::new(settings),
         Err(err) => {
             tracing::error!("{}", err.to_string());
             return Err(err);
         }
     };
 
-    server::setup_scheduled_tasks(pool.clone());
-
-    let context_providers: ContextProviders =
-        ContextProviders::new(vec![Box::new(move || Box::new(pool.clone()))]);
+    // Replays write what they parse, so they run against a copy of the database.
+    let connection_string = if settings.archive.replay {
+        match settings.archive.replay_database_url(connection_string) {
+            Ok(replay_connection_string) => {
+                tracing::info!("Replaying archived responses into {}.", settings.archive.replay_database_env);
+                replay_connection_string
+            }
+            Err(err) => {
+                tracing::error!("{}", err.to_string());
+                return Err(err);
+            }
+        }
+    } else {
+        connection_string.to_owned()
+    };
 
-    Ok(Router::new().serve_dioxus_application(
-        ServeConfigBuilder::default().context_providers(context_providers),
-        App,
-    ))
+    let pool = match init_db_connection(&connection_string) {
+        Ok(pool) => pool,
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            return Err(err);
+        }
+    };
+    server::sync_contest(&pool, &settings.contest)?;
+    let metrics = telemetry::install_recorder()?;
+    let health_routes = health::routes(pool.clone(), metrics);
+    let email_routes = email::routes(pool.clone());
+
+    let state = ServerState {
+        pool: pool.clone(),
+        settings: settings.clone(),
+        http: MediumClient::new(&settings.http)?,
+    };
+    let registry = server::setup_scheduled_tasks(state.clone());
+    let feed_routes = feeds::routes(state.clone());
+    let card_routes = cards::routes(state.clone());
+    let sitemap_routes = sitemap::routes(state.clone());
+    let voting_routes = voting::routes(state.clone());
+
+    let context_providers: ContextProviders = ContextProviders::new(vec![
+        Box::new(move || Box::new(pool.clone())),
+        Box::new(move || Box::new(settings.clone())),
+        Box::new(move || Box::new(state.clone())),
+        Box::new(move || Box::new(registry.clone())),
+    ]);
+
+    Ok(Router::new()
+        .merge(health_routes)
+        .merge(email_routes)
+        .merge(feed_routes)
+        .merge(card_routes)
+        .merge(sitemap_routes)
+        .merge(voting_routes)
+        .serve_dioxus_application(
+            ServeConfigBuilder::default().context_providers(context_providers),
+            App,
+        )
+        .layer(middleware::from_fn(embed::embed_headers))
+        .layer(middleware::from_fn(telemetry::track_server_fn)))
 }
 
 #[cfg(not(feature = "server"))]
@@ -56,7 +114,7 @@ async fn main() {
         listener,
         standalone_setup(&connection_string)
             .expect("Failed to create dioxus router")
-            .into_make_service(),
+            .into_make_service_with_connect_info::<std::net::SocketAddr>(),
     )
         .await
         .unwrap();
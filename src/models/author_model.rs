@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use diesel::prelude::*;
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::authors))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct Author {
+    pub id: String,
+    pub name: String,
+    pub username: String,
+    pub bio: String,
+    pub image_id: String,
+    pub follower_count: i32,
+    pub details_last_updated_at: chrono::DateTime<chrono::Local>,
+}
+
+impl Author {
+    pub fn avatar_url(&self, size: u32) -> String {
+        format!(
+            "https://miro.medium.com/v2/resize:fill:{size}:{size}/{}",
+            self.image_id
+        )
+    }
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::authors))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct InsertAuthor {
+    pub id: String,
+    pub name: String,
+    pub username: String,
+    pub bio: String,
+    pub image_id: String,
+    pub follower_count: i32,
+    pub details_last_updated_at: chrono::DateTime<chrono::Local>,
+}
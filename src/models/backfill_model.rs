@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use diesel::prelude::*;
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::backfill_progress))]
+#[cfg_attr(feature = "server", diesel(primary_key(contest_id)))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct BackfillProgress {
+    pub contest_id: i32,
+    pub cursor: Option<String>,
+    pub pages_fetched: i32,
+    pub completed_at: Option<chrono::DateTime<chrono::Local>>,
+    pub updated_at: chrono::DateTime<chrono::Local>,
+}
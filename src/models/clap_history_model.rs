@@ -1,8 +1,11 @@
+use crate::models::Submission;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[cfg(feature = "server")]
 use diesel::prelude::*;
 
+#[cfg(feature = "server")]
 #[derive(Debug, Default, Serialize, Deserialize)]
 #[cfg_attr(feature = "server", derive(Insertable))]
 #[cfg_attr(feature = "server", diesel(table_name = crate::schema::clap_history))]
@@ -11,3 +14,46 @@ pub struct InsertClapHistory {
     pub guid: String,
     pub clap_count: i32,
 }
+
+/// Clap counts of one moment of a [`ClapRace`].
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClapRaceFrame {
+    pub at: chrono::DateTime<chrono::Local>,
+    /// Claps by guid of the entries seen by then.
+    pub clap_counts: HashMap<String, i32>,
+}
+
+/// The clap counts of a contest's entries over its duration, for the animated clap chart.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClapRace {
+    pub submissions: Vec<Submission>,
+    /// Ordered by time, evenly spaced from the start of the contest to its end or now.
+    pub frames: Vec<ClapRaceFrame>,
+}
+
+/// One ranked entry of a [`StandingsFrame`]. The submission itself is in
+/// [`TimeTravel::submissions`], only what changes over time is repeated per frame.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameEntry {
+    pub guid: String,
+    pub clap_count: i32,
+    pub score: f64,
+    pub breakdown: String,
+    pub rank: usize,
+    pub tie_break: Option<String>,
+}
+
+/// The ranking of one moment of a [`TimeTravel`], ordered by rank.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StandingsFrame {
+    pub at: chrono::DateTime<chrono::Local>,
+    pub entries: Vec<FrameEntry>,
+}
+
+/// The rankings of a contest over its duration, for playing the race back.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeTravel {
+    pub submissions: Vec<Submission>,
+    /// Ordered by time, evenly spaced from the start of the contest to its end or now.
+    pub frames: Vec<StandingsFrame>,
+}
@@ -0,0 +1,46 @@
+use crate::models::{Category, Scoring};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use diesel::prelude::*;
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::contests))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct Contest {
+    pub id: i32,
+    pub slug: String,
+    pub title: String,
+    pub publication: String,
+    pub starts_at: chrono::DateTime<chrono::Local>,
+    pub ends_at: Option<chrono::DateTime<chrono::Local>>,
+    /// Serialized [`Scoring`], read it with [`Contest::scoring`].
+    pub scoring: String,
+    /// Jury scores and comments are hidden until then.
+    pub jury_published_at: Option<chrono::DateTime<chrono::Local>>,
+}
+
+impl Contest {
+    pub fn scoring(&self) -> Scoring {
+        serde_json::from_str(&self.scoring).unwrap_or_default()
+    }
+
+    /// Path of the PNG standings card of `category`, or of the whole contest.
+    pub fn card_path(&self, category: Option<Category>) -> String {
+        match category.filter(|category| *category != Category::None) {
+            Some(category) => format!("/cards/{}/{}/png", self.slug, category.slug()),
+            None => format!("/cards/{}/png", self.slug),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SiteInfo {
+    pub contest: Contest,
+    pub version: String,
+    /// `leaderboard.public_url` without a trailing slash.
+    pub public_url: String,
+    /// Whether readers' choice voting is enabled.
+    pub voting: bool,
+}
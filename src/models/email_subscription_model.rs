@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use diesel::prelude::*;
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::email_subscriptions))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct EmailSubscription {
+    pub id: i32,
+    pub username: String,
+    pub email: String,
+    pub token: String,
+    pub created_at: chrono::DateTime<chrono::Local>,
+    pub confirmation_sent_at: Option<chrono::DateTime<chrono::Local>>,
+    pub confirmed_at: Option<chrono::DateTime<chrono::Local>>,
+    pub unsubscribed_at: Option<chrono::DateTime<chrono::Local>>,
+    pub last_sent_at: Option<chrono::DateTime<chrono::Local>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Insertable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::email_subscriptions))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct InsertEmailSubscription {
+    pub username: String,
+    pub email: String,
+    pub confirmation_sent_at: Option<chrono::DateTime<chrono::Local>>,
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use diesel::prelude::*;
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::ingest_errors))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct IngestError {
+    pub id: i32,
+    pub job_name: String,
+    pub guid: Option<String>,
+    pub detail: String,
+    pub error: String,
+    pub created_at: chrono::DateTime<chrono::Local>,
+    /// Body of a response that could not be parsed.
+    pub raw_response: Option<String>,
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Insertable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::ingest_errors))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct InsertIngestError {
+    pub job_name: String,
+    pub guid: Option<String>,
+    pub detail: String,
+    pub error: String,
+    pub raw_response: Option<String>,
+}
@@ -0,0 +1,139 @@
+#[cfg(feature = "server")]
+use diesel::{
+    backend::Backend,
+    deserialize,
+    deserialize::{FromSql, FromSqlRow},
+    prelude::*,
+    serialize::{Output, ToSql},
+    sql_types::SmallInt,
+    AsExpression,
+};
+use serde::{Deserialize, Serialize};
+
+/// Outcome of the last attempt to ingest a single post.
+#[repr(i16)]
+#[derive(Debug, Default, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(FromSqlRow, AsExpression))]
+#[cfg_attr(feature = "server", diesel(sql_type = SmallInt))]
+pub enum ItemStatus {
+    #[default]
+    Ingested = 0,
+    /// Failed with a transient error, retried at `next_attempt_at`.
+    Retrying = 1,
+    /// Failed permanently or ran out of attempts.
+    Failed = 2,
+}
+
+#[cfg(feature = "server")]
+impl<DB> FromSql<SmallInt, DB> for ItemStatus
+    where
+        DB: Backend,
+        i16: FromSql<SmallInt, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        match i16::from_sql(bytes)? {
+            0 => Ok(ItemStatus::Ingested),
+            1 => Ok(ItemStatus::Retrying),
+            2 => Ok(ItemStatus::Failed),
+            x => Err(format!("Unrecognized variant {}", x).into()),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl<DB> ToSql<SmallInt, DB> for ItemStatus
+    where DB: Backend,
+          i16: ToSql<SmallInt, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> diesel::serialize::Result {
+        match self {
+            ItemStatus::Ingested => 0.to_sql(out),
+            ItemStatus::Retrying => 1.to_sql(out),
+            ItemStatus::Failed => 2.to_sql(out),
+        }
+    }
+}
+
+#[repr(i16)]
+#[derive(Debug, Default, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(FromSqlRow, AsExpression))]
+#[cfg_attr(feature = "server", diesel(sql_type = SmallInt))]
+pub enum FailureKind {
+    #[default]
+    Network = 0,
+    RateLimited = 1,
+    NotFound = 2,
+    Paywalled = 3,
+    SchemaChanged = 4,
+    Db = 5,
+    /// Replay mode found no archived response for the request.
+    NotArchived = 6,
+}
+
+impl FailureKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FailureKind::Network => "Network",
+            FailureKind::RateLimited => "Rate limited",
+            FailureKind::NotFound => "Not found",
+            FailureKind::Paywalled => "Member-only",
+            FailureKind::SchemaChanged => "Schema changed",
+            FailureKind::Db => "Database",
+            FailureKind::NotArchived => "Not archived",
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl<DB> FromSql<SmallInt, DB> for FailureKind
+    where
+        DB: Backend,
+        i16: FromSql<SmallInt, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        match i16::from_sql(bytes)? {
+            0 => Ok(FailureKind::Network),
+            1 => Ok(FailureKind::RateLimited),
+            2 => Ok(FailureKind::NotFound),
+            3 => Ok(FailureKind::Paywalled),
+            4 => Ok(FailureKind::SchemaChanged),
+            5 => Ok(FailureKind::Db),
+            6 => Ok(FailureKind::NotArchived),
+            x => Err(format!("Unrecognized variant {}", x).into()),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl<DB> ToSql<SmallInt, DB> for FailureKind
+    where DB: Backend,
+          i16: ToSql<SmallInt, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> diesel::serialize::Result {
+        match self {
+            FailureKind::Network => 0.to_sql(out),
+            FailureKind::RateLimited => 1.to_sql(out),
+            FailureKind::NotFound => 2.to_sql(out),
+            FailureKind::Paywalled => 3.to_sql(out),
+            FailureKind::SchemaChanged => 4.to_sql(out),
+            FailureKind::Db => 5.to_sql(out),
+            FailureKind::NotArchived => 6.to_sql(out),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::ingest_items))]
+#[cfg_attr(feature = "server", diesel(primary_key(guid)))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct IngestItem {
+    pub guid: String,
+    pub job_name: String,
+    pub status: ItemStatus,
+    pub failure: Option<FailureKind>,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: Option<chrono::DateTime<chrono::Local>>,
+    pub updated_at: chrono::DateTime<chrono::Local>,
+}
@@ -0,0 +1,97 @@
+#[cfg(feature = "server")]
+use diesel::{
+    backend::Backend,
+    deserialize,
+    deserialize::{FromSql, FromSqlRow},
+    prelude::*,
+    serialize::{Output, ToSql},
+    sql_types::SmallInt,
+    AsExpression,
+};
+use serde::{Deserialize, Serialize};
+
+#[repr(i16)]
+#[derive(Debug, Default, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(FromSqlRow, AsExpression))]
+#[cfg_attr(feature = "server", diesel(sql_type = SmallInt))]
+pub enum JobStatus {
+    #[default]
+    Running = 0,
+    Succeeded = 1,
+    Failed = 2,
+}
+
+#[cfg(feature = "server")]
+impl<DB> FromSql<SmallInt, DB> for JobStatus
+    where
+        DB: Backend,
+        i16: FromSql<SmallInt, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        match i16::from_sql(bytes)? {
+            0 => Ok(JobStatus::Running),
+            1 => Ok(JobStatus::Succeeded),
+            2 => Ok(JobStatus::Failed),
+            x => Err(format!("Unrecognized variant {}", x).into()),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl<DB> ToSql<SmallInt, DB> for JobStatus
+    where DB: Backend,
+          i16: ToSql<SmallInt, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> diesel::serialize::Result {
+        match self {
+            JobStatus::Running => 0.to_sql(out),
+            JobStatus::Succeeded => 1.to_sql(out),
+            JobStatus::Failed => 2.to_sql(out),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::job_schedules))]
+#[cfg_attr(feature = "server", diesel(primary_key(name)))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct JobSchedule {
+    pub name: String,
+    pub interval_seconds: i32,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::job_runs))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct JobRun {
+    pub id: i32,
+    pub job_name: String,
+    pub started_at: chrono::DateTime<chrono::Local>,
+    pub finished_at: Option<chrono::DateTime<chrono::Local>>,
+    pub status: JobStatus,
+    pub items_processed: i32,
+    pub error: Option<String>,
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Insertable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::job_runs))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct InsertJobRun {
+    pub job_name: String,
+    pub status: JobStatus,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobOverview {
+    pub name: String,
+    pub interval_seconds: Option<i64>,
+    pub running: bool,
+    pub last_run: Option<JobRun>,
+    pub next_run: Option<chrono::DateTime<chrono::Local>>,
+}
@@ -0,0 +1,75 @@
+use crate::models::{Contest, Submission};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use diesel::prelude::*;
+
+/// Something the jury scores, e.g. originality, from 0 to `max`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JuryCriterion {
+    pub name: String,
+    pub max: i32,
+    /// Share of the criterion in a judge's total.
+    pub weight: f64,
+}
+
+impl Default for JuryCriterion {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            max: 10,
+            weight: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::judge_scores))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct JudgeScore {
+    pub id: i32,
+    pub guid: String,
+    pub judge: String,
+    pub criterion: String,
+    pub score: i32,
+    pub comment: String,
+    pub updated_at: chrono::DateTime<chrono::Local>,
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Insertable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::judge_scores))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct InsertJudgeScore {
+    pub guid: String,
+    pub judge: String,
+    pub criterion: String,
+    pub score: i32,
+    pub comment: String,
+}
+
+/// The jury's verdict on one submission.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JuryResult {
+    pub guid: String,
+    /// Judges who scored the submission.
+    pub judges: usize,
+    /// Mean of the judges' weighted totals, from 0 to 100.
+    pub score: f64,
+    /// Mean score per criterion, on the scale of the criterion.
+    pub criteria: Vec<(String, f64)>,
+}
+
+/// Everything a judge needs to score the current contest.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JuryBallot {
+    pub judge: String,
+    pub contest: Contest,
+    pub criteria: Vec<JuryCriterion>,
+    pub entries: Vec<Submission>,
+    /// The judge's own scores so far.
+    pub scores: Vec<JudgeScore>,
+}
@@ -1,7 +1,35 @@
+pub mod author_model;
+#[cfg(feature = "server")]
+pub mod backfill_model;
 pub mod clap_history_model;
+pub mod contest_model;
+#[cfg(feature = "server")]
+pub mod email_subscription_model;
+pub mod ingest_error_model;
+pub mod ingest_item_model;
+pub mod job_model;
+pub mod jury_model;
+pub mod notification_model;
+pub mod reader_model;
+pub mod scoring_model;
+pub mod statistics_model;
 pub mod submission_model;
 
+pub use author_model::*;
+#[cfg(feature = "server")]
+pub use backfill_model::*;
 pub use clap_history_model::*;
+pub use contest_model::*;
+#[cfg(feature = "server")]
+pub use email_subscription_model::*;
+pub use ingest_error_model::*;
+pub use ingest_item_model::*;
+pub use job_model::*;
+pub use jury_model::*;
+pub use notification_model::*;
+pub use reader_model::*;
+pub use scoring_model::*;
+pub use statistics_model::*;
 pub use submission_model::*;
 
 #[cfg(feature = "server")]
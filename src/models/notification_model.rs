@@ -0,0 +1,86 @@
+#[cfg(feature = "server")]
+use diesel::{
+    backend::Backend,
+    deserialize,
+    deserialize::{FromSql, FromSqlRow},
+    prelude::*,
+    serialize::{Output, ToSql},
+    sql_types::SmallInt,
+    AsExpression,
+};
+use serde::{Deserialize, Serialize};
+
+#[repr(i16)]
+#[derive(Debug, Default, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(FromSqlRow, AsExpression))]
+#[cfg_attr(feature = "server", diesel(sql_type = SmallInt))]
+pub enum DeliveryStatus {
+    #[default]
+    Pending = 0,
+    Delivered = 1,
+    /// Gave up after the last retry.
+    Failed = 2,
+}
+
+#[cfg(feature = "server")]
+impl<DB> FromSql<SmallInt, DB> for DeliveryStatus
+    where
+        DB: Backend,
+        i16: FromSql<SmallInt, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        match i16::from_sql(bytes)? {
+            0 => Ok(DeliveryStatus::Pending),
+            1 => Ok(DeliveryStatus::Delivered),
+            2 => Ok(DeliveryStatus::Failed),
+            x => Err(format!("Unrecognized variant {}", x).into()),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl<DB> ToSql<SmallInt, DB> for DeliveryStatus
+    where DB: Backend,
+          i16: ToSql<SmallInt, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> diesel::serialize::Result {
+        match self {
+            DeliveryStatus::Pending => 0.to_sql(out),
+            DeliveryStatus::Delivered => 1.to_sql(out),
+            DeliveryStatus::Failed => 2.to_sql(out),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::notification_deliveries))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct NotificationDelivery {
+    pub id: i32,
+    pub target: String,
+    pub event: String,
+    pub dedup_key: String,
+    pub message: String,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: chrono::DateTime<chrono::Local>,
+    pub created_at: chrono::DateTime<chrono::Local>,
+    pub delivered_at: Option<chrono::DateTime<chrono::Local>>,
+    /// Standings card attached to the message.
+    pub image_url: Option<String>,
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Insertable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::notification_deliveries))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct InsertNotificationDelivery {
+    pub target: String,
+    pub event: String,
+    pub dedup_key: String,
+    pub message: String,
+    pub image_url: Option<String>,
+}
@@ -0,0 +1,74 @@
+use crate::models::{Category, Contest, Submission};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use diesel::prelude::*;
+
+/// A reader who logs in by email to vote. Never sent to the client, it holds the session token.
+#[cfg(feature = "server")]
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "server", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::readers))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct Reader {
+    pub id: i32,
+    pub email: String,
+    pub login_token: Option<String>,
+    pub login_requested_at: Option<chrono::DateTime<chrono::Local>>,
+    pub session_token: Option<String>,
+    pub verified_at: Option<chrono::DateTime<chrono::Local>>,
+    pub created_at: chrono::DateTime<chrono::Local>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::votes))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct Vote {
+    pub id: i32,
+    pub reader_id: i32,
+    pub guid: String,
+    pub contest_id: i32,
+    pub category: Category,
+    /// `None` when the client address is not known.
+    pub ip: Option<String>,
+    /// Why the fraud check held the vote back, flagged votes are not counted.
+    pub flag: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Local>,
+}
+
+#[cfg(feature = "server")]
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(Insertable))]
+#[cfg_attr(feature = "server", diesel(table_name = crate::schema::votes))]
+#[cfg_attr(feature = "server", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct InsertVote {
+    pub reader_id: i32,
+    pub guid: String,
+    pub contest_id: i32,
+    pub category: Category,
+    pub ip: Option<String>,
+    pub flag: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadersChoiceEntry {
+    pub submission: Submission,
+    /// Votes that passed the fraud check.
+    pub votes: i64,
+}
+
+/// The readers' choice board, and the ballot of the logged-in reader.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadersChoice {
+    pub contest: Contest,
+    /// Most votes first, per category.
+    pub entries: Vec<ReadersChoiceEntry>,
+    pub votes_per_category: i64,
+    /// Votes are closed once the contest has ended.
+    pub open: bool,
+    /// Email of the logged-in reader.
+    pub reader: Option<String>,
+    /// Guids the reader voted for.
+    pub my_votes: Vec<String>,
+}
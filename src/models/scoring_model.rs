@@ -0,0 +1,79 @@
+use crate::models::Submission;
+use serde::{Deserialize, Serialize};
+
+/// How the entries of a contest are ranked.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Scoring {
+    #[default]
+    Claps,
+    /// Claps divided by the days since publication, so late entries are not at a disadvantage.
+    ClapsPerDay,
+    /// Claps per 1000 words, so long pieces are not at an advantage.
+    ClapsPerThousandWords,
+    /// Readers who clapped, regardless of how often.
+    UniqueClappers,
+    /// Share of the category's best clap count and the jury score, weighted.
+    Blend { clap_weight: f64, judge_weight: f64 },
+}
+
+impl Scoring {
+    /// One of each kind, for selecting a strategy.
+    pub const KINDS: [Scoring; 5] = [
+        Scoring::Claps,
+        Scoring::ClapsPerDay,
+        Scoring::ClapsPerThousandWords,
+        Scoring::UniqueClappers,
+        Scoring::Blend {
+            clap_weight: 0.5,
+            judge_weight: 0.5,
+        },
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scoring::Claps => "Claps",
+            Scoring::ClapsPerDay => "Claps per day",
+            Scoring::ClapsPerThousandWords => "Claps per 1000 words",
+            Scoring::UniqueClappers => "Unique clappers",
+            Scoring::Blend { .. } => "Claps and jury",
+        }
+    }
+}
+
+/// How entries with the same score in a category are ranked.
+#[cfg(feature = "server")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TieBreak {
+    /// Tied entries share the rank, the next rank is skipped (1, 1, 3).
+    #[default]
+    Shared,
+    /// The entry that reached its clap count first ranks higher.
+    Earliest,
+    /// The shorter entry ranks higher.
+    FewerWords,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoredSubmission {
+    pub submission: Submission,
+    pub score: f64,
+    /// How the score was computed, e.g. `312 claps / 12 days`.
+    pub breakdown: String,
+    /// Rank within the category, starting at 1. Tied entries may share it.
+    pub rank: usize,
+    /// How a tie on the score was resolved, if there was one.
+    pub tie_break: Option<String>,
+}
+
+impl ScoredSubmission {
+    /// The score in a few words, for places without room for the breakdown.
+    pub fn short_score(&self, scoring: Scoring) -> String {
+        match scoring {
+            Scoring::Claps => format!("{} claps", self.submission.clap_count),
+            Scoring::UniqueClappers => format!("{} clappers", self.score as i64),
+            _ => format!("{:.1} points", self.score),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::models::Category;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use diesel::{prelude::*, sql_types::*};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AuthorRanking {
+    #[default]
+    TotalClaps,
+    Entries,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(QueryableByName))]
+pub struct AuthorTotals {
+    #[cfg_attr(feature = "server", diesel(sql_type = Text))]
+    pub author_id: String,
+    #[cfg_attr(feature = "server", diesel(sql_type = Text))]
+    pub name: String,
+    #[cfg_attr(feature = "server", diesel(sql_type = Text))]
+    pub username: String,
+    #[cfg_attr(feature = "server", diesel(sql_type = BigInt))]
+    pub total_claps: i64,
+    #[cfg_attr(feature = "server", diesel(sql_type = BigInt))]
+    pub entries: i64,
+    #[cfg_attr(feature = "server", diesel(sql_type = BigInt))]
+    pub contests: i64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(QueryableByName))]
+pub struct CategoryWins {
+    #[cfg_attr(feature = "server", diesel(sql_type = Text))]
+    pub author_id: String,
+    #[cfg_attr(feature = "server", diesel(sql_type = Text))]
+    pub name: String,
+    #[cfg_attr(feature = "server", diesel(sql_type = Text))]
+    pub username: String,
+    #[cfg_attr(feature = "server", diesel(sql_type = SmallInt))]
+    pub category: Category,
+    #[cfg_attr(feature = "server", diesel(sql_type = BigInt))]
+    pub wins: i64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(QueryableByName))]
+pub struct ContestParticipation {
+    #[cfg_attr(feature = "server", diesel(sql_type = Integer))]
+    pub contest_id: i32,
+    #[cfg_attr(feature = "server", diesel(sql_type = Text))]
+    pub title: String,
+    #[cfg_attr(feature = "server", diesel(sql_type = SmallInt))]
+    pub category: Category,
+    #[cfg_attr(feature = "server", diesel(sql_type = BigInt))]
+    pub entries: i64,
+    #[cfg_attr(feature = "server", diesel(sql_type = BigInt))]
+    pub authors: i64,
+    #[cfg_attr(feature = "server", diesel(sql_type = BigInt))]
+    pub total_claps: i64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(QueryableByName))]
+pub struct ContestClapActivity {
+    #[cfg_attr(feature = "server", diesel(sql_type = Integer))]
+    pub contest_id: i32,
+    #[cfg_attr(feature = "server", diesel(sql_type = Text))]
+    pub title: String,
+    #[cfg_attr(feature = "server", diesel(sql_type = Date))]
+    pub day: chrono::NaiveDate,
+    #[cfg_attr(feature = "server", diesel(sql_type = BigInt))]
+    pub claps_gained: i64,
+}
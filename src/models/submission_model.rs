@@ -22,6 +22,38 @@ pub enum Category {
     PersonalEssay = 3,
 }
 
+impl Category {
+    pub const ALL: [Category; 4] = [
+        Category::None,
+        Category::Poetry,
+        Category::Fiction,
+        Category::PersonalEssay,
+    ];
+
+    /// Used in urls.
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Category::None => "unsorted",
+            Category::Poetry => "poetry",
+            Category::Fiction => "fiction",
+            Category::PersonalEssay => "personal-essay",
+        }
+    }
+
+    pub fn from_slug(slug: &str) -> Option<Category> {
+        Category::ALL.into_iter().find(|category| category.slug() == slug)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::None => "Unsorted",
+            Category::Poetry => "Poetry",
+            Category::Fiction => "Fiction",
+            Category::PersonalEssay => "Personal Essay",
+        }
+    }
+}
+
 #[cfg(feature = "server")]
 impl<DB> FromSql<SmallInt, DB> for Category
     where
@@ -73,9 +105,13 @@ pub struct Submission {
     pub clap_count_last_updated_at: chrono::DateTime<chrono::Local>,
     pub details_last_updated_at: chrono::DateTime<chrono::Local>,
     pub category: Category,
+    pub contest_id: i32,
+    pub author_id: Option<String>,
+    /// Readers who clapped, `None` unless the contest is scored by unique clappers.
+    pub voter_count: Option<i32>,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "server", derive(Insertable))]
 #[cfg_attr(feature = "server", diesel(table_name = crate::schema::submissions))]
 #[cfg_attr(feature = "server", diesel(primary_key(guid)))]
@@ -90,4 +126,7 @@ pub struct InsertSubmission {
     pub title: String,
     pub img_id: String,
     pub word_count: i32,
+    pub category: Category,
+    pub contest_id: i32,
+    pub author_id: Option<String>,
 }
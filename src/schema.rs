@@ -1,5 +1,37 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    api_responses (id) {
+        id -> Int4,
+        kind -> Text,
+        request_key -> Text,
+        body -> Bytea,
+        fetched_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    authors (id) {
+        id -> Text,
+        name -> Text,
+        username -> Text,
+        bio -> Text,
+        image_id -> Text,
+        follower_count -> Int4,
+        details_last_updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    backfill_progress (contest_id) {
+        contest_id -> Int4,
+        cursor -> Nullable<Text>,
+        pages_fetched -> Int4,
+        completed_at -> Nullable<Timestamptz>,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     clap_history (id) {
         id -> Int4,
@@ -9,6 +41,137 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    contests (id) {
+        id -> Int4,
+        slug -> Text,
+        title -> Text,
+        publication -> Text,
+        starts_at -> Timestamptz,
+        ends_at -> Nullable<Timestamptz>,
+        scoring -> Text,
+        jury_published_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    email_subscriptions (id) {
+        id -> Int4,
+        username -> Text,
+        email -> Text,
+        token -> Text,
+        created_at -> Timestamptz,
+        confirmation_sent_at -> Nullable<Timestamptz>,
+        confirmed_at -> Nullable<Timestamptz>,
+        unsubscribed_at -> Nullable<Timestamptz>,
+        last_sent_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    ingest_errors (id) {
+        id -> Int4,
+        job_name -> Text,
+        guid -> Nullable<Text>,
+        detail -> Text,
+        error -> Text,
+        created_at -> Timestamptz,
+        raw_response -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    ingest_items (guid) {
+        guid -> Text,
+        job_name -> Text,
+        status -> Int2,
+        failure -> Nullable<Int2>,
+        attempts -> Int4,
+        last_error -> Nullable<Text>,
+        next_attempt_at -> Nullable<Timestamptz>,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    job_leases (job_name) {
+        job_name -> Text,
+        holder -> Text,
+        acquired_at -> Timestamptz,
+        expires_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    job_runs (id) {
+        id -> Int4,
+        job_name -> Text,
+        started_at -> Timestamptz,
+        finished_at -> Nullable<Timestamptz>,
+        status -> Int2,
+        items_processed -> Int4,
+        error -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    job_schedules (name) {
+        name -> Text,
+        interval_seconds -> Int4,
+        enabled -> Bool,
+    }
+}
+
+diesel::table! {
+    judge_scores (id) {
+        id -> Int4,
+        guid -> Text,
+        judge -> Text,
+        criterion -> Text,
+        score -> Int4,
+        comment -> Text,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    login_requests (id) {
+        id -> Int4,
+        email -> Text,
+        ip -> Nullable<Text>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    notification_deliveries (id) {
+        id -> Int4,
+        target -> Text,
+        event -> Text,
+        dedup_key -> Text,
+        message -> Text,
+        status -> Int2,
+        attempts -> Int4,
+        last_error -> Nullable<Text>,
+        next_attempt_at -> Timestamptz,
+        created_at -> Timestamptz,
+        delivered_at -> Nullable<Timestamptz>,
+        image_url -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    readers (id) {
+        id -> Int4,
+        email -> Text,
+        login_token -> Nullable<Text>,
+        login_requested_at -> Nullable<Timestamptz>,
+        session_token -> Nullable<Text>,
+        verified_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     submissions (guid) {
         guid -> Text,
@@ -23,12 +186,60 @@ diesel::table! {
         clap_count_last_updated_at -> Timestamptz,
         details_last_updated_at -> Timestamptz,
         category -> Int2,
+        contest_id -> Int4,
+        author_id -> Nullable<Text>,
+        voter_count -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    subscription_requests (id) {
+        id -> Int4,
+        email -> Text,
+        ip -> Nullable<Text>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    votes (id) {
+        id -> Int4,
+        reader_id -> Int4,
+        guid -> Text,
+        contest_id -> Int4,
+        category -> Int2,
+        ip -> Nullable<Text>,
+        flag -> Nullable<Text>,
+        created_at -> Timestamptz,
     }
 }
 
+diesel::joinable!(backfill_progress -> contests (contest_id));
 diesel::joinable!(clap_history -> submissions (guid));
+diesel::joinable!(judge_scores -> submissions (guid));
+diesel::joinable!(submissions -> authors (author_id));
+diesel::joinable!(submissions -> contests (contest_id));
+diesel::joinable!(votes -> contests (contest_id));
+diesel::joinable!(votes -> readers (reader_id));
+diesel::joinable!(votes -> submissions (guid));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    api_responses,
+    authors,
+    backfill_progress,
     clap_history,
+    contests,
+    email_subscriptions,
+    ingest_errors,
+    ingest_items,
+    job_leases,
+    job_runs,
+    job_schedules,
+    judge_scores,
+    login_requests,
+    notification_deliveries,
+    readers,
     submissions,
+    subscription_requests,
+    votes,
 );
@@ -0,0 +1,63 @@
+use crate::server::failure::IngestFailure;
+use crate::server::ServerState;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use dioxus::logger::tracing;
+
+/// Stores a raw response body if the archive is enabled. Errors are logged, archiving never
+/// fails a fetch.
+pub(crate) fn store(state: &ServerState, kind: &str, request_key: &str, body: &[u8]) {
+    use crate::db::api_responses::dsl;
+
+    if !state.settings.archive.enabled || state.settings.archive.replay {
+        return;
+    }
+
+    let stored = state.pool.get().map_err(anyhow::Error::from).and_then(|mut connection| {
+        Ok(diesel::insert_into(dsl::api_responses)
+            .values((
+                dsl::kind.eq(kind),
+                dsl::request_key.eq(request_key),
+                dsl::body.eq(body),
+            ))
+            .execute(&mut connection)?)
+    });
+
+    if let Err(err) = stored {
+        tracing::error!("Error archiving {} response for {}: {}", kind, request_key, err);
+    }
+}
+
+/// The newest archived response for the request, or the newest one before `archive.replay_as_of`.
+pub(crate) fn load(state: &ServerState, kind: &str, request_key: &str) -> Result<Vec<u8>, IngestFailure> {
+    use crate::db::api_responses::dsl;
+    let mut connection = state.pool.get()?;
+
+    let mut query = dsl::api_responses
+        .filter(dsl::kind.eq(kind))
+        .filter(dsl::request_key.eq(request_key))
+        .into_boxed();
+    if let Some(as_of) = state.settings.archive.replay_as_of {
+        query = query.filter(dsl::fetched_at.le(as_of));
+    }
+
+    query
+        .order_by(dsl::fetched_at.desc())
+        .select(dsl::body)
+        .first::<Vec<u8>>(&mut connection)
+        .optional()?
+        .ok_or_else(|| IngestFailure::NotArchived(format!("{} {}", kind, request_key)))
+}
+
+/// Deletes responses older than `archive.retention_days`.
+pub(crate) async fn prune_response_archive(state: &ServerState) -> anyhow::Result<usize> {
+    use crate::db::api_responses::dsl;
+    let mut connection = state.pool.get()?;
+
+    let threshold = chrono::Local::now()
+        - chrono::TimeDelta::days(state.settings.archive.retention_days as i64);
+    let deleted = diesel::delete(dsl::api_responses.filter(dsl::fetched_at.lt(threshold)))
+        .execute(&mut connection)?;
+
+    tracing::info!("Pruned {} archived responses.", deleted);
+    Ok(deleted)
+}
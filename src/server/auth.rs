@@ -0,0 +1,25 @@
+use crate::server::settings::Settings;
+use dioxus::prelude::ServerFnError;
+
+/// Moderation endpoints are guarded by the shared secret in `ADMIN_TOKEN`. Without it they are
+/// disabled.
+pub fn require_admin(token: &str) -> Result<(), ServerFnError> {
+    match std::env::var("ADMIN_TOKEN") {
+        Ok(expected) if !expected.is_empty() && expected == token => Ok(()),
+        _ => Err(ServerFnError::new("Unauthorized")),
+    }
+}
+
+/// Judges log in with the token in the environment variable named by their `token_env`.
+/// Returns the name of the judge.
+pub fn require_judge(settings: &Settings, token: &str) -> Result<String, ServerFnError> {
+    settings
+        .jury
+        .judges
+        .iter()
+        .find(|judge| {
+            matches!(std::env::var(&judge.token_env), Ok(expected) if !expected.is_empty() && expected == token)
+        })
+        .map(|judge| judge.name.clone())
+        .ok_or(ServerFnError::new("Unauthorized"))
+}
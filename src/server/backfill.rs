@@ -0,0 +1,163 @@
+use crate::models::BackfillProgress;
+use crate::server::failure::{self, IngestFailure};
+use crate::server::graphql::tag_archive_query::{
+    PostConnectionResponse, TagArchiveFeedQuery, TagArchiveFeedResult, TimeRange,
+};
+use crate::server::graphql::GraphQlRequest;
+use crate::server::{current_contest, fetch_graphql, ingest_post, ServerState};
+use diesel::{
+    ExpressionMethods, OptionalExtension, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper,
+};
+use dioxus::logger::tracing;
+use std::time::Duration;
+
+const PAGE_SIZE: i32 = 25;
+const PAGE_DELAY: Duration = Duration::from_secs(2);
+
+/// Pages through the tag archive, newest first, and ingests every publication post within the
+/// window of the current contest. The cursor is stored after each page, so an interrupted
+/// backfill continues where it stopped.
+pub(crate) async fn backfill_archive(state: &ServerState) -> anyhow::Result<usize> {
+    use crate::db::backfill_progress::dsl;
+    use crate::db::submissions::dsl as dsls;
+    let settings = &state.settings;
+    let mut connection = state.pool.get()?;
+
+    let contest = current_contest(&mut connection, settings)?;
+    let progress = dsl::backfill_progress
+        .find(contest.id)
+        .select(BackfillProgress::as_select())
+        .first(&mut connection)
+        .optional()?;
+
+    if let Some(BackfillProgress { completed_at: Some(completed_at), .. }) = &progress {
+        tracing::info!("Backfill of {} completed at {}.", contest.slug, completed_at);
+        return Ok(0);
+    }
+
+    let window_start = contest.starts_at.timestamp_millis();
+    let window_end = contest.ends_at.map_or(i64::MAX, |ends_at| ends_at.timestamp_millis());
+    let mut cursor = progress.and_then(|progress| progress.cursor);
+    let mut inserted = 0;
+
+    tracing::info!("Backfilling {} from cursor {:?}.", contest.slug, cursor);
+
+    loop {
+        let page = fetch_archive_page(state, cursor.as_deref()).await?;
+        let mut reached_window_start = false;
+
+        for edge in page.edges {
+            let post = edge.node;
+            if post.first_published_at < window_start {
+                reached_window_start = true;
+                break;
+            }
+            if post.first_published_at > window_end {
+                continue;
+            }
+            let publication = post.collection.as_ref().map(|collection| collection.slug.as_str());
+            if publication != Some(settings.medium.publication_slug.as_str()) {
+                continue;
+            }
+            if dsls::submissions
+                .find(&post.id)
+                .count()
+                .get_result::<i64>(&mut connection)?
+                > 0
+            {
+                continue;
+            }
+
+            if !failure::should_attempt(&mut connection, &post.id)? {
+                continue;
+            }
+
+            match ingest_post(
+                &mut connection,
+                state,
+                "backfill_archive",
+                contest.id,
+                &post.id,
+                None,
+                String::new(),
+            )
+            .await
+            {
+                Ok(rows) => inserted += rows,
+                // Stop before the cursor moves on, the page is fetched again on the next run.
+                Err(err @ IngestFailure::RateLimited { .. }) => return Err(err.into()),
+                // Failed posts are retried by `update_rss` according to their retry policy.
+                Err(_) => continue,
+            }
+        }
+
+        let completed = reached_window_start
+            || !page.page_info.has_next_page
+            || page.page_info.end_cursor.is_none();
+        cursor = page.page_info.end_cursor;
+        save_progress(&mut connection, contest.id, cursor.as_deref(), completed)?;
+
+        if completed {
+            tracing::info!("Backfill of {} completed.", contest.slug);
+            break;
+        }
+        tokio::time::sleep(PAGE_DELAY).await;
+    }
+
+    Ok(inserted)
+}
+
+async fn fetch_archive_page(
+    state: &ServerState,
+    after: Option<&str>,
+) -> anyhow::Result<PostConnectionResponse> {
+    let settings = &state.settings;
+
+    Ok(fetch_graphql::<_, TagArchiveFeedResult>(
+        state,
+        "tag_archive",
+        &format!("{}/{}", settings.medium.tag_slug, after.unwrap_or("first")),
+        GraphQlRequest::from(TagArchiveFeedQuery {
+            tag_slug: &settings.medium.tag_slug,
+            time_range: TimeRange { kind: "ALL_TIME" },
+            sort_order: "NEWEST",
+            first: PAGE_SIZE,
+            after,
+        }),
+    )
+    .await?
+    .tag_from_slug
+    .sorted_feed)
+}
+
+fn save_progress(
+    connection: &mut PgConnection,
+    contest_id: i32,
+    cursor: Option<&str>,
+    completed: bool,
+) -> anyhow::Result<()> {
+    use crate::db::backfill_progress::dsl;
+
+    let now = chrono::Local::now();
+    let completed_at = completed.then_some(now);
+
+    diesel::insert_into(dsl::backfill_progress)
+        .values((
+            dsl::contest_id.eq(contest_id),
+            dsl::cursor.eq(cursor),
+            dsl::pages_fetched.eq(1),
+            dsl::completed_at.eq(completed_at),
+            dsl::updated_at.eq(now),
+        ))
+        .on_conflict(dsl::contest_id)
+        .do_update()
+        .set((
+            dsl::cursor.eq(cursor),
+            dsl::pages_fetched.eq(dsl::pages_fetched + 1),
+            dsl::completed_at.eq(completed_at),
+            dsl::updated_at.eq(now),
+        ))
+        .execute(connection)?;
+
+    Ok(())
+}
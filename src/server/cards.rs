@@ -0,0 +1,268 @@
+use crate::models::{Category, Contest};
+use crate::server::scoring;
+use crate::server::settings::Settings;
+use crate::server::ServerState;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use diesel::{ExpressionMethods, OptionalExtension, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use dioxus::logger::tracing;
+use resvg::{tiny_skia, usvg};
+use serde::Deserialize;
+use std::sync::{Arc, OnceLock};
+
+/// The size Open Graph and Twitter cards are displayed at.
+const WIDTH: u32 = 1200;
+const HEIGHT: u32 = 630;
+const DEFAULT_TOP: usize = 5;
+const MAX_TOP: usize = 10;
+
+/// Standings cards for link previews and announcements:
+/// `/cards/{contest}/{svg|png}` shows the leader of every category,
+/// `/cards/{contest}/{category}/{svg|png}?top=N` the top N of one category.
+pub fn routes(state: ServerState) -> Router {
+    Router::new()
+        .route("/cards/:contest/:format", get(contest_card))
+        .route("/cards/:contest/:category/:format", get(category_card))
+        .with_state(state)
+}
+
+#[derive(Deserialize)]
+struct CardQuery {
+    top: Option<usize>,
+}
+
+struct CardRow {
+    label: String,
+    title: String,
+    author: String,
+    /// The score in a few words, e.g. `312 claps`.
+    score: String,
+}
+
+struct Card {
+    title: String,
+    subtitle: String,
+    rows: Vec<CardRow>,
+}
+
+async fn contest_card(
+    State(state): State<ServerState>,
+    Path((contest, format)): Path<(String, String)>,
+) -> Response {
+    card(state, contest, None, 1, format).await
+}
+
+async fn category_card(
+    State(state): State<ServerState>,
+    Path((contest, category, format)): Path<(String, String, String)>,
+    Query(query): Query<CardQuery>,
+) -> Response {
+    match Category::from_slug(&category) {
+        Some(category) => {
+            let top = query.top.unwrap_or(DEFAULT_TOP).clamp(1, MAX_TOP);
+            card(state, contest, Some(category), top, format).await
+        }
+        None => (StatusCode::NOT_FOUND, "Unknown category").into_response(),
+    }
+}
+
+async fn card(state: ServerState, contest: String, category: Option<Category>, top: usize, format: String) -> Response {
+    if format != "svg" && format != "png" {
+        return (StatusCode::NOT_FOUND, "Unknown card format, use svg or png").into_response();
+    }
+
+    let rendered = tokio::task::spawn_blocking(move || -> anyhow::Result<Option<(&'static str, Vec<u8>)>> {
+        let mut connection = state.pool.get()?;
+        let Some(card) = load_card(&mut connection, &state.settings, &contest, category, top)? else {
+            return Ok(None);
+        };
+        let svg = render_svg(&card);
+        if format == "svg" {
+            Ok(Some(("image/svg+xml", svg.into_bytes())))
+        } else {
+            Ok(Some(("image/png", rasterize(&svg)?)))
+        }
+    })
+    .await;
+
+    match rendered {
+        Ok(Ok(Some((content_type, body)))) => (
+            [
+                (header::CONTENT_TYPE, content_type),
+                (header::CACHE_CONTROL, "public, max-age=600"),
+            ],
+            body,
+        )
+            .into_response(),
+        Ok(Ok(None)) => (StatusCode::NOT_FOUND, "Unknown contest").into_response(),
+        Ok(Err(err)) => {
+            tracing::error!("Rendering card failed: {}", err);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+        Err(err) => {
+            tracing::error!("Rendering card failed: {}", err);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+fn load_card(
+    connection: &mut PgConnection,
+    settings: &Settings,
+    slug: &str,
+    category: Option<Category>,
+    top: usize,
+) -> anyhow::Result<Option<Card>> {
+    use crate::db::contests::dsl as dslc;
+
+    let Some(contest) = dslc::contests
+        .filter(dslc::slug.eq(slug))
+        .select(Contest::as_select())
+        .first(connection)
+        .optional()?
+    else {
+        return Ok(None);
+    };
+
+    let scoring = contest.scoring();
+    let ranked = scoring::score_contest(connection, settings, &contest)?;
+
+    let card = match category {
+        Some(category) => Card {
+            title: contest.title.clone(),
+            subtitle: format!("{} · Top {}", category.name(), top),
+            rows: ranked
+                .iter()
+                .filter(|entry| entry.submission.category == category)
+                .take(top)
+                .map(|entry| CardRow {
+                    label: format!("{}.", entry.rank),
+                    title: entry.submission.title.clone(),
+                    author: entry.submission.realname.clone(),
+                    score: entry.short_score(scoring),
+                })
+                .collect(),
+        },
+        None => Card {
+            title: contest.title.clone(),
+            subtitle: format!("{} · Current leaders", contest.publication),
+            rows: Category::ALL
+                .into_iter()
+                .filter(|category| *category != Category::None)
+                .filter_map(|category| {
+                    ranked
+                        .iter()
+                        .find(|entry| entry.submission.category == category)
+                        .map(|entry| CardRow {
+                            label: category.name().to_owned(),
+                            title: entry.submission.title.clone(),
+                            author: entry.submission.realname.clone(),
+                            score: entry.short_score(scoring),
+                        })
+                })
+                .collect(),
+        },
+    };
+
+    Ok(Some(card))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Shortens `text` to `max` characters, so that long titles do not run into the clap count.
+fn truncate(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max.saturating_sub(1)) {
+        Some((index, _)) if text.chars().count() > max => format!("{}…", &text[..index]),
+        _ => text.to_owned(),
+    }
+}
+
+fn render_svg(card: &Card) -> String {
+    let top = 190;
+    let row_height = if card.rows.is_empty() {
+        0
+    } else {
+        ((HEIGHT as usize - top - 30) / card.rows.len()).min(90)
+    };
+    let label_width = if card.rows.iter().any(|row| row.label.len() > 3) { 230 } else { 70 };
+
+    let mut svg = format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}">
+<defs><linearGradient id="bg" x1="0" y1="0" x2="1" y2="1"><stop offset="0" stop-color="#1d2330"/><stop offset="1" stop-color="#2b3445"/></linearGradient></defs>
+<rect width="{WIDTH}" height="{HEIGHT}" fill="url(#bg)"/>
+<g font-family="DejaVu Sans, Liberation Sans, Arial, sans-serif">
+<text x="60" y="95" font-size="52" font-weight="bold" fill="#ffffff">{}</text>
+<text x="60" y="145" font-size="28" fill="#9fb3c8">{}</text>
+"##,
+        escape(&truncate(&card.title, 36)),
+        escape(&card.subtitle)
+    );
+
+    if card.rows.is_empty() {
+        svg.push_str(r##"<text x="60" y="260" font-size="32" fill="#ffffff">No entries yet.</text>"##);
+    }
+    for (index, row) in card.rows.iter().enumerate() {
+        let y = top + index * row_height;
+        let title_size = (row_height * 2 / 5).clamp(18, 30);
+        svg.push_str(&format!(
+            r##"<line x1="60" y1="{y}" x2="{}" y2="{y}" stroke="#3d4a5e"/>
+<text x="60" y="{}" font-size="{title_size}" font-weight="bold" fill="#f5b942">{}</text>
+<text x="{}" y="{}" font-size="{title_size}" fill="#ffffff">{}</text>
+<text x="{}" y="{}" font-size="{}" fill="#9fb3c8">{}</text>
+<text x="{}" y="{}" font-size="{title_size}" text-anchor="end" fill="#ffffff">{}</text>
+"##,
+            WIDTH - 60,
+            y + row_height / 2,
+            escape(&row.label),
+            60 + label_width,
+            y + row_height / 2,
+            escape(&truncate(&row.title, if label_width > 100 { 42 } else { 52 })),
+            60 + label_width,
+            y + row_height / 2 + title_size,
+            title_size * 3 / 4,
+            escape(&row.author),
+            WIDTH - 60,
+            y + row_height / 2,
+            escape(&row.score)
+        ));
+    }
+
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+/// System fonts, loaded once. Without any installed font the PNG cards have no text.
+fn fonts() -> Arc<usvg::fontdb::Database> {
+    static FONTS: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
+    FONTS
+        .get_or_init(|| {
+            let mut fonts = usvg::fontdb::Database::new();
+            fonts.load_system_fonts();
+            if fonts.is_empty() {
+                tracing::warn!("No system fonts found, share cards will render without text.");
+            }
+            Arc::new(fonts)
+        })
+        .clone()
+}
+
+fn rasterize(svg: &str) -> anyhow::Result<Vec<u8>> {
+    let options = usvg::Options {
+        fontdb: fonts(),
+        ..Default::default()
+    };
+    let tree = usvg::Tree::from_str(svg, &options)?;
+    let mut pixmap =
+        tiny_skia::Pixmap::new(WIDTH, HEIGHT).ok_or(anyhow::anyhow!("Could not allocate the card"))?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+    Ok(pixmap.encode_png()?)
+}
@@ -0,0 +1,486 @@
+use crate::db::DbPool;
+use crate::models::{
+    Category, Contest, EmailSubscription, InsertEmailSubscription, ScoredSubmission, Scoring, Submission,
+};
+use crate::server::settings::{EmailSettings, MailTransport, Settings};
+use crate::server::{current_contest, scoring, ServerState};
+use anyhow::bail;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::routing::get;
+use axum::Router;
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, Insertable, OptionalExtension, PgConnection, QueryDsl,
+    RunQueryDsl, SelectableHelper,
+};
+use dioxus::logger::tracing;
+use lettre::message::header::{Header, HeaderName, HeaderValue};
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncFileTransport, AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::collections::HashMap;
+
+pub(crate) const JOB_NAME: &str = "send_email_digests";
+/// Confirmation links expire after a day.
+const CONFIRMATION_LINK_HOURS: i64 = 24;
+
+/// Sends emails over SMTP, or writes them to `mail_dir` with the file transport.
+pub(crate) enum Mailer {
+    Smtp(AsyncSmtpTransport<Tokio1Executor>),
+    File(AsyncFileTransport<Tokio1Executor>),
+}
+
+impl Mailer {
+    pub(crate) fn new(settings: &EmailSettings) -> anyhow::Result<Self> {
+        match settings.transport {
+            MailTransport::Smtp => {
+                let mut builder = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&settings.smtp_host)?
+                    .port(settings.smtp_port);
+                if !settings.smtp_username.is_empty() {
+                    builder = builder.credentials(Credentials::new(
+                        settings.smtp_username.clone(),
+                        settings.smtp_password.clone(),
+                    ));
+                }
+                Ok(Mailer::Smtp(builder.build()))
+            }
+            MailTransport::File => {
+                std::fs::create_dir_all(&settings.mail_dir)?;
+                Ok(Mailer::File(AsyncFileTransport::new(&settings.mail_dir)))
+            }
+        }
+    }
+
+    pub(crate) async fn send(&self, message: Message) -> anyhow::Result<()> {
+        match self {
+            Mailer::Smtp(transport) => {
+                transport.send(message).await?;
+            }
+            Mailer::File(transport) => {
+                transport.send(message).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `List-Unsubscribe`, together with `List-Unsubscribe-Post` mail clients show an unsubscribe
+/// button that POSTs to the link (RFC 8058).
+#[derive(Clone)]
+struct ListUnsubscribe(String);
+
+impl Header for ListUnsubscribe {
+    fn name() -> HeaderName {
+        HeaderName::new_from_ascii_str("List-Unsubscribe")
+    }
+
+    fn parse(s: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self(s.trim_matches(['<', '>']).to_owned()))
+    }
+
+    fn display(&self) -> HeaderValue {
+        HeaderValue::new(Self::name(), format!("<{}>", self.0))
+    }
+}
+
+#[derive(Clone)]
+struct ListUnsubscribePost;
+
+impl Header for ListUnsubscribePost {
+    fn name() -> HeaderName {
+        HeaderName::new_from_ascii_str("List-Unsubscribe-Post")
+    }
+
+    fn parse(_: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self)
+    }
+
+    fn display(&self) -> HeaderValue {
+        HeaderValue::new(Self::name(), "List-Unsubscribe=One-Click".to_owned())
+    }
+}
+
+fn link(settings: &Settings, action: &str, token: &str) -> String {
+    format!("{}/email/{}/{}", settings.leaderboard.public_url(), action, token)
+}
+
+/// Subscribes `email` to the digest of `username` and sends the confirmation link. Digests only
+/// go out once the link was opened. Like the readers' login, requests beyond the hourly limits
+/// for the address or for `ip` are refused, and a pending link is not sent again.
+pub(crate) async fn subscribe(
+    state: &ServerState,
+    username: &str,
+    email: &str,
+    ip: Option<&str>,
+) -> anyhow::Result<()> {
+    use crate::db::email_subscriptions::dsl;
+    use crate::db::submissions::dsl as dsls;
+    use crate::db::subscription_requests::dsl as dslr;
+    let settings = &state.settings.email;
+    if !settings.enabled {
+        bail!("Email digests are not enabled.");
+    }
+
+    let mailbox: Mailbox = email.trim().parse().map_err(|_| anyhow::anyhow!("{} is not a valid email address", email))?;
+    let address = mailbox.email.to_string();
+    let username = username.trim().trim_start_matches('@');
+    let mut connection = state.pool.get()?;
+
+    let an_hour_ago = chrono::Local::now() - chrono::TimeDelta::hours(1);
+    diesel::delete(dslr::subscription_requests.filter(dslr::created_at.lt(an_hour_ago))).execute(&mut connection)?;
+    let recent_for_address: i64 = dslr::subscription_requests
+        .filter(dslr::email.eq(address.to_lowercase()))
+        .count()
+        .get_result(&mut connection)?;
+    let recent_from_ip: i64 = match ip {
+        Some(ip) => dslr::subscription_requests
+            .filter(dslr::ip.eq(ip))
+            .count()
+            .get_result(&mut connection)?,
+        None => 0,
+    };
+    if recent_for_address >= settings.subscriptions_per_hour
+        || recent_from_ip >= settings.subscriptions_per_ip_per_hour
+    {
+        bail!("Too many subscription requests, please try again later.");
+    }
+
+    if dsls::submissions
+        .filter(dsls::username.eq(username))
+        .count()
+        .get_result::<i64>(&mut connection)?
+        == 0
+    {
+        bail!("There are no submissions by @{}.", username);
+    }
+
+    let existing = dsl::email_subscriptions
+        .filter(dsl::username.eq(username))
+        .filter(dsl::email.eq(&address))
+        .select(EmailSubscription::as_select())
+        .first(&mut connection)
+        .optional()?;
+    if let Some(existing) = existing {
+        if existing.confirmed_at.is_some() && existing.unsubscribed_at.is_none() {
+            return Ok(());
+        }
+        let pending = existing.confirmation_sent_at.is_some_and(|sent_at| sent_at > confirmation_cutoff());
+        if existing.confirmed_at.is_none() && pending {
+            bail!("A confirmation link was already sent, please check your inbox.");
+        }
+    }
+
+    // Subscribing again after an unsubscribe needs a new confirmation.
+    let now = chrono::Local::now();
+    let subscription = InsertEmailSubscription {
+        username: username.to_owned(),
+        email: address.clone(),
+        confirmation_sent_at: Some(now),
+    }
+    .insert_into(dsl::email_subscriptions)
+    .on_conflict((dsl::username, dsl::email))
+    .do_update()
+    .set((
+        dsl::confirmation_sent_at.eq(now),
+        dsl::confirmed_at.eq(None::<chrono::DateTime<chrono::Local>>),
+        dsl::unsubscribed_at.eq(None::<chrono::DateTime<chrono::Local>>),
+    ))
+    .returning(EmailSubscription::as_returning())
+    .get_result(&mut connection)?;
+    diesel::insert_into(dslr::subscription_requests)
+        .values((dslr::email.eq(address.to_lowercase()), dslr::ip.eq(ip)))
+        .execute(&mut connection)?;
+
+    let message = Message::builder()
+        .from(settings.from.parse()?)
+        .to(mailbox)
+        .subject(format!("Confirm your daily digest for @{}", subscription.username))
+        .body(format!(
+            "Someone, hopefully you, asked for a daily email about the leaderboard standing of @{}.\n\n\
+             Confirm the subscription: {}\n\n\
+             If this was not you, ignore this email and nothing will be sent.\n",
+            subscription.username,
+            link(&state.settings, "confirm", &subscription.token)
+        ))?;
+    Mailer::new(settings)?.send(message).await?;
+    tracing::info!("Sent digest confirmation for @{}.", subscription.username);
+
+    Ok(())
+}
+
+/// `/email/confirm/{token}` activates a subscription, `/email/unsubscribe/{token}` ends it. The
+/// unsubscribe link asks for a click so that link scanners do not unsubscribe anyone, mail
+/// clients POST to it directly.
+pub fn routes(pool: DbPool) -> Router {
+    let confirm_pool = pool.clone();
+    let unsubscribe_pool = pool;
+
+    Router::new()
+        .route(
+            "/email/confirm/:token",
+            get(move |Path(token): Path<String>| confirm(confirm_pool.clone(), token)),
+        )
+        .route(
+            "/email/unsubscribe/:token",
+            get(|Path(token): Path<String>| async move { unsubscribe_page(&token) })
+                .post(move |Path(token): Path<String>| unsubscribe(unsubscribe_pool.clone(), token)),
+        )
+}
+
+/// Confirmation links older than this are not accepted, and a new one may be requested.
+fn confirmation_cutoff() -> chrono::DateTime<chrono::Local> {
+    chrono::Local::now() - chrono::TimeDelta::hours(CONFIRMATION_LINK_HOURS)
+}
+
+async fn confirm(pool: DbPool, token: String) -> (StatusCode, Html<String>) {
+    use crate::db::email_subscriptions::dsl;
+
+    let updated = tokio::task::spawn_blocking(move || -> anyhow::Result<usize> {
+        let mut connection = pool.get()?;
+        Ok(diesel::update(
+            dsl::email_subscriptions
+                .filter(dsl::token.eq(&token))
+                .filter(dsl::confirmation_sent_at.gt(confirmation_cutoff())),
+        )
+            .set((
+                dsl::confirmed_at.eq(chrono::Local::now()),
+                dsl::unsubscribed_at.eq(None::<chrono::DateTime<chrono::Local>>),
+            ))
+            .execute(&mut connection)?)
+    })
+    .await;
+
+    match updated {
+        Ok(Ok(1)) => page(StatusCode::OK, "Subscribed. The first digest arrives with the next daily run."),
+        Ok(Ok(_)) => page(StatusCode::NOT_FOUND, "This link is not valid anymore, please subscribe again."),
+        Ok(Err(err)) => {
+            tracing::error!("Confirming subscription failed: {}", err);
+            page(StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong, please try again later.")
+        }
+        Err(err) => page(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string()),
+    }
+}
+
+fn unsubscribe_page(token: &str) -> (StatusCode, Html<String>) {
+    let token: String = token.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-').collect();
+    (
+        StatusCode::OK,
+        Html(format!(
+            "<!DOCTYPE html><html><body style=\"font-family: sans-serif\">\
+             <form method=\"post\" action=\"/email/unsubscribe/{}\">\
+             <p>Stop the daily leaderboard digest?</p>\
+             <button type=\"submit\">Unsubscribe</button>\
+             </form></body></html>",
+            token
+        )),
+    )
+}
+
+async fn unsubscribe(pool: DbPool, token: String) -> (StatusCode, Html<String>) {
+    use crate::db::email_subscriptions::dsl;
+
+    let updated = tokio::task::spawn_blocking(move || -> anyhow::Result<usize> {
+        let mut connection = pool.get()?;
+        Ok(diesel::update(dsl::email_subscriptions.filter(dsl::token.eq(&token)))
+            .set(dsl::unsubscribed_at.eq(chrono::Local::now()))
+            .execute(&mut connection)?)
+    })
+    .await;
+
+    match updated {
+        Ok(Ok(1)) => page(StatusCode::OK, "Unsubscribed. You will not get any more digests."),
+        Ok(Ok(_)) => page(StatusCode::NOT_FOUND, "This link is not valid."),
+        Ok(Err(err)) => {
+            tracing::error!("Unsubscribing failed: {}", err);
+            page(StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong, please try again later.")
+        }
+        Err(err) => page(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string()),
+    }
+}
+
+fn page(status: StatusCode, text: &str) -> (StatusCode, Html<String>) {
+    (
+        status,
+        Html(format!(
+            "<!DOCTYPE html><html><body style=\"font-family: sans-serif\"><p>{}</p></body></html>",
+            text
+        )),
+    )
+}
+
+/// Where a submission stands in its category.
+struct Standing {
+    submission: Submission,
+    /// `None` while the submission has no category.
+    rank: Option<usize>,
+    entries: usize,
+    /// The score in a few words, `None` when ranking by claps.
+    score: Option<String>,
+    /// Claps gained in the last 24 hours, `None` if there is no count from back then.
+    clap_delta: Option<i32>,
+    /// The next rank up and the score needed to draw level with it.
+    behind_next: Option<(usize, f64)>,
+}
+
+fn standings(connection: &mut PgConnection, settings: &Settings, contest: &Contest) -> anyhow::Result<Vec<Standing>> {
+    use crate::db::clap_history::dsl as dslh;
+
+    let scoring = contest.scoring();
+    let ranked = scoring::score_contest(connection, settings, contest)?;
+
+    let cutoff = chrono::Local::now() - chrono::TimeDelta::days(1);
+    let guids: Vec<&str> = ranked.iter().map(|entry| entry.submission.guid.as_str()).collect();
+    let yesterday: HashMap<String, i32> = dslh::clap_history
+        .filter(dslh::guid.eq_any(&guids))
+        .filter(dslh::timestamp.le(cutoff))
+        .order_by((dslh::guid, dslh::timestamp.desc()))
+        .distinct_on(dslh::guid)
+        .select((dslh::guid, dslh::clap_count))
+        .load::<(String, i32)>(connection)?
+        .into_iter()
+        .collect();
+
+    Ok(ranked
+        .iter()
+        .map(|entry| {
+            let submission = &entry.submission;
+            let rivals: Vec<&ScoredSubmission> = ranked
+                .iter()
+                .filter(|other| other.submission.category == submission.category)
+                .collect();
+            let next = rivals
+                .iter()
+                .filter(|other| other.rank < entry.rank)
+                .max_by_key(|other| other.rank);
+            let published_recently = submission.latest_published_at > cutoff.timestamp_millis();
+
+            Standing {
+                submission: submission.clone(),
+                rank: (submission.category != Category::None).then_some(entry.rank),
+                entries: rivals.len(),
+                score: (scoring != Scoring::Claps).then(|| entry.short_score(scoring)),
+                clap_delta: match yesterday.get(&submission.guid) {
+                    Some(claps) => Some(submission.clap_count - claps),
+                    None if published_recently => Some(submission.clap_count),
+                    None => None,
+                },
+                behind_next: next.map(|next| (next.rank, next.score - entry.score)),
+            }
+        })
+        .collect())
+}
+
+fn digest_message(
+    settings: &Settings,
+    contest: &Contest,
+    subscription: &EmailSubscription,
+    standings: &[&Standing],
+) -> anyhow::Result<Message> {
+    let unsubscribe = link(settings, "unsubscribe", &subscription.token);
+    let mut body = format!(
+        "Hi {},\n\nhere is where your entries in {} stand today:\n",
+        standings[0].submission.realname, contest.title
+    );
+
+    for standing in standings {
+        let submission = &standing.submission;
+        body.push_str(&format!("\n{} ({})\n", submission.title, submission.category.name()));
+        match standing.rank {
+            Some(rank) => body.push_str(&format!("  Rank {} of {}", rank, standing.entries)),
+            None => body.push_str("  Not sorted into a category yet"),
+        }
+        if let Some(score) = &standing.score {
+            body.push_str(&format!(" · {}", score));
+        }
+        body.push_str(&format!(" · {} claps", submission.clap_count));
+        if let Some(delta) = standing.clap_delta {
+            body.push_str(&format!(" ({:+} since yesterday)", delta));
+        }
+        body.push('\n');
+        match (standing.rank, standing.behind_next) {
+            (Some(_), Some((next_rank, behind))) if behind <= 0.0 => {
+                body.push_str(&format!("  Level with rank {}, behind on the tie-break\n", next_rank))
+            }
+            (Some(_), Some((next_rank, behind))) if contest.scoring() == Scoring::Claps => {
+                body.push_str(&format!("  {} claps behind rank {}\n", behind as i64, next_rank))
+            }
+            (Some(_), Some((next_rank, behind))) => {
+                body.push_str(&format!("  {:.1} points behind rank {}\n", behind, next_rank))
+            }
+            (Some(_), None) => body.push_str("  Leading the category\n"),
+            _ => {}
+        }
+    }
+
+    body.push_str(&format!(
+        "\nLeaderboard: {}\n\n\
+         You get this email because you subscribed to the daily digest for @{}.\n\
+         Unsubscribe: {}\n",
+        settings.leaderboard.public_url(), subscription.username, unsubscribe
+    ));
+
+    Ok(Message::builder()
+        .from(settings.email.from.parse()?)
+        .to(subscription.email.parse()?)
+        .subject(format!("{}: your standing today", contest.title))
+        .header(ListUnsubscribe(unsubscribe))
+        .header(ListUnsubscribePost)
+        .body(body)?)
+}
+
+/// Sends the daily digest to every confirmed subscription whose author has an entry in the
+/// current contest.
+pub(crate) async fn send_email_digests(state: &ServerState) -> anyhow::Result<usize> {
+    use crate::db::email_subscriptions::dsl;
+    let settings = &state.settings;
+    if !settings.email.enabled {
+        tracing::info!("Email digests are disabled.");
+        return Ok(0);
+    }
+
+    let mut connection = state.pool.get()?;
+    let contest = current_contest(&mut connection, settings)?;
+    // A little less than a day, so that a late run does not push the next digest back.
+    let due_before = chrono::Local::now() - chrono::TimeDelta::hours(20);
+
+    let subscriptions = dsl::email_subscriptions
+        .filter(dsl::confirmed_at.is_not_null())
+        .filter(dsl::unsubscribed_at.is_null())
+        .filter(dsl::last_sent_at.is_null().or(dsl::last_sent_at.le(due_before)))
+        .select(EmailSubscription::as_select())
+        .load(&mut connection)?;
+    if subscriptions.is_empty() {
+        return Ok(0);
+    }
+
+    let standings = standings(&mut connection, settings, &contest)?;
+    let mailer = Mailer::new(&settings.email)?;
+    let mut sent = 0;
+
+    for subscription in subscriptions {
+        let entries: Vec<&Standing> = standings
+            .iter()
+            .filter(|standing| standing.submission.username == subscription.username)
+            .collect();
+        if entries.is_empty() {
+            continue;
+        }
+
+        let result = match digest_message(settings, &contest, &subscription, &entries) {
+            Ok(message) => mailer.send(message).await,
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(()) => {
+                diesel::update(&subscription)
+                    .set(dsl::last_sent_at.eq(chrono::Local::now()))
+                    .execute(&mut connection)?;
+                sent += 1;
+            }
+            Err(err) => tracing::error!("Sending the digest to subscription {} failed: {}", subscription.id, err),
+        }
+    }
+
+    Ok(sent)
+}
@@ -0,0 +1,25 @@
+use axum::extract::Request;
+use axum::http::{header, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+
+/// Embeds may be framed by any site and cached briefly by browsers and proxies.
+pub async fn embed_headers(request: Request, next: Next) -> Response {
+    let is_embed = request.uri().path().starts_with("/embed/");
+    let mut response = next.run(request).await;
+
+    if is_embed && response.status().is_success() {
+        let headers = response.headers_mut();
+        headers.insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_static("public, max-age=300, stale-while-revalidate=600"),
+        );
+        headers.insert(
+            header::CONTENT_SECURITY_POLICY,
+            HeaderValue::from_static("frame-ancestors *"),
+        );
+        headers.remove(header::X_FRAME_OPTIONS);
+    }
+
+    response
+}
@@ -0,0 +1,364 @@
+use crate::models::{FailureKind, IngestItem, ItemStatus};
+use crate::server::graphql::GraphQlError;
+use diesel::{ExpressionMethods, OptionalExtension, PgConnection, QueryDsl, RunQueryDsl};
+use reqwest::{header, StatusCode};
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// Why fetching or storing a single post failed.
+#[derive(Debug)]
+pub enum IngestFailure {
+    Network(String),
+    RateLimited { retry_after: Option<Duration> },
+    NotFound,
+    Paywalled,
+    /// The response did not match the expected shape. Keeps the raw body for inspection.
+    SchemaChanged { error: String, raw_response: Option<String> },
+    Db(String),
+    /// Replay mode has no archived response for the request, retrying would not change that.
+    NotArchived(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RetryPolicy {
+    Never,
+    /// Retried after `base`, doubling with each attempt up to `max_delay`, until `max_attempts`
+    /// is reached.
+    Backoff { base: Duration, max_delay: Duration, max_attempts: i32 },
+}
+
+impl RetryPolicy {
+    /// Delay before the next attempt, `None` once the item should not be retried anymore.
+    pub fn delay(&self, attempts: i32) -> Option<Duration> {
+        match *self {
+            RetryPolicy::Never => None,
+            RetryPolicy::Backoff { base, max_delay, max_attempts } if attempts < max_attempts => {
+                Some((base * 2u32.pow(attempts.clamp(1, 10) as u32 - 1)).min(max_delay))
+            }
+            RetryPolicy::Backoff { .. } => None,
+        }
+    }
+}
+
+impl IngestFailure {
+    pub(crate) fn schema_changed(error: impl Display) -> Self {
+        IngestFailure::SchemaChanged {
+            error: error.to_string(),
+            raw_response: None,
+        }
+    }
+
+    pub fn raw_response(&self) -> Option<&str> {
+        match self {
+            IngestFailure::SchemaChanged { raw_response, .. } => raw_response.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn kind(&self) -> FailureKind {
+        match self {
+            IngestFailure::Network(_) => FailureKind::Network,
+            IngestFailure::RateLimited { .. } => FailureKind::RateLimited,
+            IngestFailure::NotFound => FailureKind::NotFound,
+            IngestFailure::Paywalled => FailureKind::Paywalled,
+            IngestFailure::SchemaChanged { .. } => FailureKind::SchemaChanged,
+            IngestFailure::Db(_) => FailureKind::Db,
+            IngestFailure::NotArchived(_) => FailureKind::NotArchived,
+        }
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        match self {
+            IngestFailure::Network(_) => RetryPolicy::Backoff {
+                base: Duration::from_secs(5 * 60),
+                max_delay: Duration::from_secs(60 * 60),
+                max_attempts: 5,
+            },
+            IngestFailure::RateLimited { retry_after } => RetryPolicy::Backoff {
+                base: retry_after.unwrap_or(Duration::from_secs(15 * 60)),
+                max_delay: retry_after.unwrap_or_default().max(Duration::from_secs(2 * 60 * 60)),
+                max_attempts: 10,
+            },
+            // Usually fixed by a deploy, so keep trying for a while.
+            IngestFailure::SchemaChanged { .. } => RetryPolicy::Backoff {
+                base: Duration::from_secs(6 * 60 * 60),
+                max_delay: Duration::from_secs(24 * 60 * 60),
+                max_attempts: 4,
+            },
+            IngestFailure::Db(_) => RetryPolicy::Backoff {
+                base: Duration::from_secs(60),
+                max_delay: Duration::from_secs(15 * 60),
+                max_attempts: 5,
+            },
+            IngestFailure::NotFound | IngestFailure::Paywalled | IngestFailure::NotArchived(_) => RetryPolicy::Never,
+        }
+    }
+
+    pub fn is_transient(&self) -> bool {
+        self.retry_policy() != RetryPolicy::Never
+    }
+
+    /// Maps a non-success HTTP response to a failure, `None` for success.
+    pub(crate) fn from_response(response: &reqwest::Response) -> Option<Self> {
+        let status = response.status();
+        match status {
+            StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE => {
+                Some(IngestFailure::RateLimited {
+                    retry_after: retry_after(response),
+                })
+            }
+            StatusCode::NOT_FOUND | StatusCode::GONE => Some(IngestFailure::NotFound),
+            StatusCode::PAYMENT_REQUIRED => Some(IngestFailure::Paywalled),
+            status if status.is_success() => None,
+            status => Some(IngestFailure::Network(format!("Unexpected status {}", status))),
+        }
+    }
+
+    /// Medium reports missing and member-only posts as GraphQL errors instead of data. They are
+    /// told apart by the error code, the message is only kept for the log.
+    pub(crate) fn from_graphql_errors(errors: &[GraphQlError]) -> Self {
+        for error in errors {
+            match error.code() {
+                Some("NOT_FOUND") => return IngestFailure::NotFound,
+                Some("FORBIDDEN" | "UNAUTHENTICATED" | "PAYMENT_REQUIRED") => return IngestFailure::Paywalled,
+                Some("RATE_LIMITED" | "TOO_MANY_REQUESTS") => return IngestFailure::RateLimited { retry_after: None },
+                _ => {}
+            }
+        }
+
+        let messages = errors
+            .iter()
+            .map(|error| match error.code() {
+                Some(code) => format!("{} ({})", error.message, code),
+                None => error.message.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+        if messages.is_empty() {
+            IngestFailure::schema_changed("Response contains neither data nor errors")
+        } else {
+            IngestFailure::schema_changed(messages)
+        }
+    }
+}
+
+impl Display for IngestFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IngestFailure::Network(err) => write!(f, "Network error: {}", err),
+            IngestFailure::RateLimited { retry_after: Some(retry_after) } => {
+                write!(f, "Rate limited, retry after {:?}", retry_after)
+            }
+            IngestFailure::RateLimited { retry_after: None } => write!(f, "Rate limited"),
+            IngestFailure::NotFound => write!(f, "Post not found"),
+            IngestFailure::Paywalled => write!(f, "Post is member-only"),
+            IngestFailure::SchemaChanged { error, .. } => write!(f, "Unexpected response: {}", error),
+            IngestFailure::Db(err) => write!(f, "Database error: {}", err),
+            IngestFailure::NotArchived(request) => write!(f, "No archived response for {}", request),
+        }
+    }
+}
+
+impl std::error::Error for IngestFailure {}
+
+impl From<reqwest::Error> for IngestFailure {
+    fn from(err: reqwest::Error) -> Self {
+        match err.status() {
+            Some(StatusCode::TOO_MANY_REQUESTS) => IngestFailure::RateLimited { retry_after: None },
+            Some(StatusCode::NOT_FOUND) => IngestFailure::NotFound,
+            _ if err.is_decode() => IngestFailure::schema_changed(err),
+            _ => IngestFailure::Network(err.to_string()),
+        }
+    }
+}
+
+impl From<serde_json::Error> for IngestFailure {
+    fn from(err: serde_json::Error) -> Self {
+        IngestFailure::schema_changed(err)
+    }
+}
+
+impl From<rss::Error> for IngestFailure {
+    fn from(err: rss::Error) -> Self {
+        IngestFailure::schema_changed(err)
+    }
+}
+
+impl From<diesel::result::Error> for IngestFailure {
+    fn from(err: diesel::result::Error) -> Self {
+        IngestFailure::Db(err.to_string())
+    }
+}
+
+impl From<diesel::r2d2::PoolError> for IngestFailure {
+    fn from(err: diesel::r2d2::PoolError) -> Self {
+        IngestFailure::Db(err.to_string())
+    }
+}
+
+/// `Retry-After` as either seconds or an HTTP date.
+pub(crate) fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    let value = response.headers().get(header::RETRY_AFTER)?.to_str().ok()?.trim();
+
+    match value.parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => chrono::DateTime::parse_from_rfc2822(value)
+            .ok()
+            .and_then(|at| (at.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().ok()),
+    }
+}
+
+/// Whether the post should be fetched now: it is unknown, or a retry is due.
+pub(crate) fn should_attempt(connection: &mut PgConnection, guid: &str) -> anyhow::Result<bool> {
+    use crate::db::ingest_items::dsl;
+
+    let item = dsl::ingest_items
+        .find(guid)
+        .select((dsl::status, dsl::next_attempt_at))
+        .first::<(ItemStatus, Option<chrono::DateTime<chrono::Local>>)>(connection)
+        .optional()?;
+
+    Ok(match item {
+        None => true,
+        Some((ItemStatus::Retrying, next_attempt_at)) => {
+            next_attempt_at.is_none_or(|next_attempt_at| next_attempt_at <= chrono::Local::now())
+        }
+        Some((ItemStatus::Ingested, _)) | Some((ItemStatus::Failed, _)) => false,
+    })
+}
+
+/// Items whose retry is due.
+pub(crate) fn due_retries(connection: &mut PgConnection) -> anyhow::Result<Vec<IngestItem>> {
+    use crate::db::ingest_items::dsl;
+    use diesel::SelectableHelper;
+
+    Ok(dsl::ingest_items
+        .filter(dsl::status.eq(ItemStatus::Retrying))
+        .filter(dsl::next_attempt_at.le(chrono::Local::now()))
+        .select(IngestItem::as_select())
+        .load(connection)?)
+}
+
+pub(crate) fn mark_ingested(
+    connection: &mut PgConnection,
+    job_name: &str,
+    guid: &str,
+) -> anyhow::Result<()> {
+    use crate::db::ingest_items::dsl;
+    let now = chrono::Local::now();
+
+    diesel::insert_into(dsl::ingest_items)
+        .values((
+            dsl::guid.eq(guid),
+            dsl::job_name.eq(job_name),
+            dsl::status.eq(ItemStatus::Ingested),
+            dsl::attempts.eq(1),
+            dsl::updated_at.eq(now),
+        ))
+        .on_conflict(dsl::guid)
+        .do_update()
+        .set((
+            dsl::job_name.eq(job_name),
+            dsl::status.eq(ItemStatus::Ingested),
+            dsl::failure.eq(None::<FailureKind>),
+            dsl::attempts.eq(dsl::attempts + 1),
+            dsl::last_error.eq(None::<String>),
+            dsl::next_attempt_at.eq(None::<chrono::DateTime<chrono::Local>>),
+            dsl::updated_at.eq(now),
+        ))
+        .execute(connection)?;
+
+    Ok(())
+}
+
+/// Stores the failed attempt and schedules the next one according to the retry policy.
+pub(crate) fn mark_failed(
+    connection: &mut PgConnection,
+    job_name: &str,
+    guid: &str,
+    failure: &IngestFailure,
+) -> anyhow::Result<ItemStatus> {
+    use crate::db::ingest_items::dsl;
+    let now = chrono::Local::now();
+
+    let attempts = dsl::ingest_items
+        .find(guid)
+        .select(dsl::attempts)
+        .first::<i32>(connection)
+        .optional()?
+        .unwrap_or(0)
+        + 1;
+    let next_attempt_at = match failure.retry_policy().delay(attempts) {
+        Some(delay) => Some(now + chrono::TimeDelta::from_std(delay)?),
+        None => None,
+    };
+    let status = match next_attempt_at {
+        Some(_) => ItemStatus::Retrying,
+        None => ItemStatus::Failed,
+    };
+
+    diesel::insert_into(dsl::ingest_items)
+        .values((
+            dsl::guid.eq(guid),
+            dsl::job_name.eq(job_name),
+            dsl::status.eq(status),
+            dsl::failure.eq(failure.kind()),
+            dsl::attempts.eq(attempts),
+            dsl::last_error.eq(failure.to_string()),
+            dsl::next_attempt_at.eq(next_attempt_at),
+            dsl::updated_at.eq(now),
+        ))
+        .on_conflict(dsl::guid)
+        .do_update()
+        .set((
+            dsl::job_name.eq(job_name),
+            dsl::status.eq(status),
+            dsl::failure.eq(failure.kind()),
+            dsl::attempts.eq(attempts),
+            dsl::last_error.eq(failure.to_string()),
+            dsl::next_attempt_at.eq(next_attempt_at),
+            dsl::updated_at.eq(now),
+        ))
+        .execute(connection)?;
+
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(message: &str, code: Option<&str>) -> GraphQlError {
+        serde_json::from_value(serde_json::json!({
+            "message": message,
+            "extensions": { "code": code },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn classifies_graphql_errors_by_code() {
+        assert!(matches!(
+            IngestFailure::from_graphql_errors(&[error("Post not available", Some("NOT_FOUND"))]),
+            IngestFailure::NotFound
+        ));
+        assert!(matches!(
+            IngestFailure::from_graphql_errors(&[error("Nope", Some("FORBIDDEN"))]),
+            IngestFailure::Paywalled
+        ));
+        assert!(matches!(
+            IngestFailure::from_graphql_errors(&[error("Member not found", None)]),
+            IngestFailure::SchemaChanged { .. }
+        ));
+        assert!(matches!(IngestFailure::from_graphql_errors(&[]), IngestFailure::SchemaChanged { .. }));
+    }
+
+    #[test]
+    fn caps_the_backoff() {
+        let policy = IngestFailure::RateLimited { retry_after: None }.retry_policy();
+        assert_eq!(policy.delay(1), Some(Duration::from_secs(15 * 60)));
+        assert_eq!(policy.delay(2), Some(Duration::from_secs(30 * 60)));
+        assert_eq!(policy.delay(9), Some(Duration::from_secs(2 * 60 * 60)));
+        assert_eq!(policy.delay(10), None);
+    }
+}
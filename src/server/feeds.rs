@@ -0,0 +1,333 @@
+use crate::models::{Category, Contest, ScoredSubmission, Submission};
+use crate::server::scoring;
+use crate::server::settings::Settings;
+use crate::server::ServerState;
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use chrono::{DateTime, TimeZone, Utc};
+use diesel::{ExpressionMethods, OptionalExtension, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use dioxus::logger::tracing;
+
+/// Newest submissions per feed.
+const FEED_LENGTH: i64 = 50;
+/// Entries per category in the standings item.
+const STANDINGS_LENGTH: usize = 5;
+
+/// RSS 2.0 and Atom feeds of new submissions, per contest and per category:
+/// `/feeds/{contest}/{rss|atom}` and `/feeds/{contest}/{category}/{rss|atom}`.
+pub fn routes(state: ServerState) -> Router {
+    Router::new()
+        .route("/feeds/:contest/:format", get(contest_feed))
+        .route("/feeds/:contest/:category/:format", get(category_feed))
+        .with_state(state)
+}
+
+enum Format {
+    Rss,
+    Atom,
+}
+
+impl Format {
+    fn parse(format: &str) -> Option<Format> {
+        match format {
+            "rss" => Some(Format::Rss),
+            "atom" => Some(Format::Atom),
+            _ => None,
+        }
+    }
+}
+
+struct Feed {
+    contest: Contest,
+    category: Option<Category>,
+    /// Newest first.
+    submissions: Vec<Submission>,
+    /// Leaders per category, best ranked first.
+    standings: Vec<(Category, Vec<ScoredSubmission>)>,
+}
+
+impl Feed {
+    fn title(&self) -> String {
+        match self.category {
+            Some(category) => format!("{} – {}", self.contest.title, category.name()),
+            None => self.contest.title.clone(),
+        }
+    }
+
+    fn path(&self, format: &str) -> String {
+        match self.category {
+            Some(category) => format!("/feeds/{}/{}/{}", self.contest.slug, category.slug(), format),
+            None => format!("/feeds/{}/{}", self.contest.slug, format),
+        }
+    }
+
+    /// The standings item changes once a day, so readers see it as a new entry every day.
+    fn standings_date(&self) -> DateTime<Utc> {
+        let today = Utc::now().date_naive().and_hms_opt(0, 0, 0).expect("Midnight is a valid time");
+        Utc.from_utc_datetime(&today)
+    }
+
+    fn standings_text(&self) -> String {
+        self.standings
+            .iter()
+            .map(|(category, leaders)| {
+                let lines = leaders
+                    .iter()
+                    .map(|entry| {
+                        format!(
+                            "{}. {} by {} ({})",
+                            entry.rank,
+                            entry.submission.title,
+                            entry.submission.realname,
+                            entry.short_score(self.contest.scoring())
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                format!("{}\n{}", category.name(), lines)
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+async fn contest_feed(
+    State(state): State<ServerState>,
+    Path((contest, format)): Path<(String, String)>,
+) -> Response {
+    feed(state, contest, None, format).await
+}
+
+async fn category_feed(
+    State(state): State<ServerState>,
+    Path((contest, category, format)): Path<(String, String, String)>,
+) -> Response {
+    match Category::from_slug(&category) {
+        Some(category) => feed(state, contest, Some(category), format).await,
+        None => (StatusCode::NOT_FOUND, "Unknown category").into_response(),
+    }
+}
+
+async fn feed(state: ServerState, contest: String, category: Option<Category>, format: String) -> Response {
+    let Some(format) = Format::parse(&format) else {
+        return (StatusCode::NOT_FOUND, "Unknown feed format, use rss or atom").into_response();
+    };
+
+    let settings = state.settings.clone();
+    let loaded = tokio::task::spawn_blocking(move || -> anyhow::Result<Option<Feed>> {
+        let mut connection = state.pool.get()?;
+        load_feed(&mut connection, &state.settings, &contest, category)
+    })
+    .await;
+
+    let feed = match loaded {
+        Ok(Ok(Some(feed))) => feed,
+        Ok(Ok(None)) => return (StatusCode::NOT_FOUND, "Unknown contest").into_response(),
+        Ok(Err(err)) => {
+            tracing::error!("Building feed failed: {}", err);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+        Err(err) => {
+            tracing::error!("Building feed failed: {}", err);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let (content_type, body) = match format {
+        Format::Rss => ("application/rss+xml; charset=utf-8", rss_feed(&settings, &feed)),
+        Format::Atom => ("application/atom+xml; charset=utf-8", atom_feed(&settings, &feed)),
+    };
+
+    (
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CACHE_CONTROL, "public, max-age=900"),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+fn load_feed(
+    connection: &mut PgConnection,
+    settings: &Settings,
+    slug: &str,
+    category: Option<Category>,
+) -> anyhow::Result<Option<Feed>> {
+    use crate::db::contests::dsl as dslc;
+    use crate::db::submissions::dsl;
+
+    let Some(contest) = dslc::contests
+        .filter(dslc::slug.eq(slug))
+        .select(Contest::as_select())
+        .first(connection)
+        .optional()?
+    else {
+        return Ok(None);
+    };
+
+    let mut query = dsl::submissions
+        .filter(dsl::contest_id.eq(contest.id))
+        .filter(dsl::username.ne_all(&settings.leaderboard.excluded_usernames))
+        .into_boxed();
+    if let Some(category) = category {
+        query = query.filter(dsl::category.eq(category));
+    }
+    let submissions = query
+        .order_by(dsl::latest_published_at.desc())
+        .limit(FEED_LENGTH)
+        .select(Submission::as_select())
+        .load(connection)?;
+
+    let ranked = scoring::score_contest(connection, settings, &contest)?;
+
+    let standings = Category::ALL
+        .into_iter()
+        .filter(|candidate| *candidate != Category::None && category.is_none_or(|category| category == *candidate))
+        .map(|category| {
+            let leaders: Vec<ScoredSubmission> = ranked
+                .iter()
+                .filter(|entry| entry.submission.category == category)
+                .take(STANDINGS_LENGTH)
+                .cloned()
+                .collect();
+            (category, leaders)
+        })
+        .filter(|(_, leaders)| !leaders.is_empty())
+        .collect();
+
+    Ok(Some(Feed {
+        contest,
+        category,
+        submissions,
+        standings,
+    }))
+}
+
+fn published_at(submission: &Submission) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(submission.latest_published_at)
+        .single()
+        .unwrap_or_default()
+}
+
+fn post_url(submission: &Submission) -> String {
+    format!("https://medium.com/p/{}", submission.guid)
+}
+
+fn summary(submission: &Submission) -> String {
+    format!(
+        "{} by {} · {} · {} claps · {} words",
+        submission.title,
+        submission.realname,
+        submission.category.name(),
+        submission.clap_count,
+        submission.word_count
+    )
+}
+
+fn rss_feed(settings: &Settings, feed: &Feed) -> String {
+    use rss::{ChannelBuilder, GuidBuilder, ItemBuilder};
+    let site = settings.leaderboard.public_url();
+
+    let mut items = Vec::new();
+    if !feed.standings.is_empty() {
+        let date = feed.standings_date();
+        items.push(
+            ItemBuilder::default()
+                .title(format!("Standings on {}", date.format("%B %-d, %Y")))
+                .link(site.to_owned())
+                .description(feed.standings_text())
+                .pub_date(date.to_rfc2822())
+                .guid(
+                    GuidBuilder::default()
+                        .value(format!("{}{}#standings-{}", site, feed.path("rss"), date.format("%Y-%m-%d")))
+                        .permalink(false)
+                        .build(),
+                )
+                .build(),
+        );
+    }
+    items.extend(feed.submissions.iter().map(|submission| {
+        ItemBuilder::default()
+            .title(submission.title.clone())
+            .link(post_url(submission))
+            .author(submission.realname.clone())
+            .category(rss::Category {
+                name: submission.category.name().to_owned(),
+                domain: None,
+            })
+            .description(summary(submission))
+            .pub_date(published_at(submission).to_rfc2822())
+            .guid(GuidBuilder::default().value(post_url(submission)).permalink(true).build())
+            .build()
+    }));
+
+    ChannelBuilder::default()
+        .title(feed.title())
+        .link(site.to_owned())
+        .description(format!("New submissions and standings of the {} contest.", feed.contest.title))
+        .generator(format!("medium_leaderboard {}", settings.leaderboard.display_version))
+        .items(items)
+        .build()
+        .to_string()
+}
+
+fn atom_feed(settings: &Settings, feed: &Feed) -> String {
+    use atom_syndication::{EntryBuilder, FeedBuilder, LinkBuilder, PersonBuilder, Text};
+    let site = settings.leaderboard.public_url();
+
+    let mut entries = Vec::new();
+    if !feed.standings.is_empty() {
+        let date = feed.standings_date();
+        entries.push(
+            EntryBuilder::default()
+                .id(format!("{}{}#standings-{}", site, feed.path("atom"), date.format("%Y-%m-%d")))
+                .title(format!("Standings on {}", date.format("%B %-d, %Y")))
+                .updated(date)
+                .link(LinkBuilder::default().href(site.to_owned()).build())
+                .summary(Some(Text::plain(feed.standings_text())))
+                .build(),
+        );
+    }
+    entries.extend(feed.submissions.iter().map(|submission| {
+        EntryBuilder::default()
+            .id(post_url(submission))
+            .title(submission.title.clone())
+            .updated(published_at(submission))
+            .published(Some(published_at(submission).into()))
+            .author(PersonBuilder::default().name(submission.realname.clone()).build())
+            .link(LinkBuilder::default().href(post_url(submission)).build())
+            .category(
+                atom_syndication::CategoryBuilder::default()
+                    .term(submission.category.slug().to_owned())
+                    .label(Some(submission.category.name().to_owned()))
+                    .build(),
+            )
+            .summary(Some(Text::plain(summary(submission))))
+            .build()
+    }));
+
+    let updated = entries
+        .iter()
+        .map(|entry| *entry.updated())
+        .max()
+        .unwrap_or_else(|| Utc::now().into());
+
+    FeedBuilder::default()
+        .id(format!("{}{}", site, feed.path("atom")))
+        .title(feed.title())
+        .updated(updated)
+        .link(
+            LinkBuilder::default()
+                .href(format!("{}{}", site, feed.path("atom")))
+                .rel("self".to_owned())
+                .build(),
+        )
+        .link(LinkBuilder::default().href(site.to_owned()).build())
+        .entries(entries)
+        .build()
+        .to_string()
+}
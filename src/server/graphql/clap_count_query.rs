@@ -1,4 +1,4 @@
-use crate::server::graphql::{GraphQlRequest, GraphQlResponse};
+use crate::server::graphql::{GraphQlRequest, PostResult};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Debug)]
@@ -8,7 +8,7 @@ pub struct ClapCountQuery<'a> {
     pub(crate) include_first_boosted_at: bool,
 }
 
-pub type ClapCountResult = Vec<GraphQlResponse<ClapCountResponse>>;
+pub type ClapCountResult = PostResult<ClapCountResponse>;
 
 impl<'a> From<ClapCountQuery<'a>> for GraphQlRequest<'a, ClapCountQuery<'a>> {
     fn from(variables: ClapCountQuery<'a>) -> GraphQlRequest<'a, ClapCountQuery<'a>> {
@@ -1,9 +1,14 @@
+use crate::server::failure::IngestFailure;
+use crate::server::http::MediumClient;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 pub mod clap_count_query;
+pub(crate) mod self_check;
 pub mod story_details_query;
-
-pub const GRAPHQL_ENDPOINT: &str = "https://medium.com/_/graphql";
+pub mod tag_archive_query;
+pub mod user_profile_query;
+pub mod voter_count_query;
 
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -14,12 +19,119 @@ pub struct GraphQlRequest<'a, V: Serialize> {
 }
 
 #[derive(Deserialize, Debug)]
-pub struct GraphQlResponse<T> {
-    pub(crate) data: PostResult<T>,
+pub struct GraphQlResponse<D> {
+    pub(crate) data: Option<D>,
+    #[serde(default)]
+    pub(crate) errors: Vec<GraphQlError>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GraphQlError {
+    pub(crate) message: String,
+    #[serde(default)]
+    pub(crate) extensions: Option<GraphQlErrorExtensions>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GraphQlErrorExtensions {
+    #[serde(default)]
+    pub(crate) code: Option<String>,
+}
+
+impl GraphQlError {
+    pub(crate) fn code(&self) -> Option<&str> {
+        self.extensions.as_ref()?.code.as_deref()
+    }
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct PostResult<T> {
-    pub(crate) post_result: T,
+    pub(crate) post_result: PostUnion<T>,
+}
+
+/// `postResult` is a union, anything but a `Post` means the post is unavailable.
+#[derive(Deserialize, Debug)]
+#[serde(tag = "__typename")]
+pub enum PostUnion<T> {
+    Post(T),
+    #[serde(other)]
+    Unavailable,
+}
+
+impl<T> PostResult<T> {
+    pub(crate) fn into_post(self) -> Result<T, IngestFailure> {
+        match self.post_result {
+            PostUnion::Post(post) => Ok(post),
+            PostUnion::Unavailable => Err(IngestFailure::NotFound),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserResult<T> {
+    pub(crate) user_result: UserUnion<T>,
+}
+
+/// `userResult` is a union as well, suspended and deleted accounts are not a `User`.
+#[derive(Deserialize, Debug)]
+#[serde(tag = "__typename")]
+pub enum UserUnion<T> {
+    User(T),
+    #[serde(other)]
+    Unavailable,
+}
+
+impl<T> UserResult<T> {
+    pub(crate) fn into_user(self) -> Result<T, IngestFailure> {
+        match self.user_result {
+            UserUnion::User(user) => Ok(user),
+            UserUnion::Unavailable => Err(IngestFailure::NotFound),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TagResult<T> {
+    pub(crate) tag_from_slug: T,
+}
+
+/// Raw responses kept for inspection are cut off after this many bytes.
+const MAX_RAW_RESPONSE_LEN: usize = 64 * 1024;
+
+/// Sends a single query and returns the response body as is.
+pub(crate) async fn fetch_raw<V: Serialize>(
+    client: &MediumClient,
+    endpoint: &str,
+    request: GraphQlRequest<'_, V>,
+) -> Result<Vec<u8>, IngestFailure> {
+    let response = client.post_json(endpoint, &vec![request]).await?;
+    if let Some(failure) = IngestFailure::from_response(&response) {
+        return Err(failure);
+    }
+
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// Parses a batched response. If it does not match `D`, the raw body is kept in the failure.
+pub(crate) fn parse<D: DeserializeOwned>(body: &[u8]) -> Result<D, IngestFailure> {
+    let schema_changed = |error: String| IngestFailure::SchemaChanged {
+        error,
+        raw_response: Some(
+            String::from_utf8_lossy(&body[..body.len().min(MAX_RAW_RESPONSE_LEN)]).into_owned(),
+        ),
+    };
+
+    let response = serde_json::from_slice::<Vec<GraphQlResponse<D>>>(body)
+        .map_err(|err| schema_changed(err.to_string()))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| schema_changed("Empty graphql response".to_owned()))?;
+
+    match response.data {
+        Some(data) => Ok(data),
+        None => Err(IngestFailure::from_graphql_errors(&response.errors)),
+    }
 }
@@ -0,0 +1,286 @@
+use crate::models::Submission;
+use crate::server::failure::IngestFailure;
+use crate::server::graphql::clap_count_query::{ClapCountQuery, ClapCountResult};
+use crate::server::graphql::story_details_query::{PostPageQuery, PostPageResult};
+use crate::server::graphql::tag_archive_query::{TagArchiveFeedQuery, TagArchiveFeedResult, TimeRange};
+use crate::server::graphql::user_profile_query::{UserProfileQuery, UserProfileResult};
+use crate::server::graphql::{fetch_raw, parse, GraphQlRequest};
+use crate::server::http::MediumClient;
+use crate::server::telemetry::record_schema_drift;
+use crate::server::{record_ingest_error, ServerState};
+use anyhow::anyhow;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl, SelectableHelper};
+use dioxus::logger::tracing;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+pub(crate) const JOB_NAME: &str = "check_graphql_schema";
+
+const POST_PAGE_FIXTURE: &str = include_str!("fixtures/post_page.json");
+const CLAP_COUNT_FIXTURE: &str = include_str!("fixtures/clap_count.json");
+const USER_PROFILE_FIXTURE: &str = include_str!("fixtures/user_profile.json");
+const TAG_ARCHIVE_FIXTURE: &str = include_str!("fixtures/tag_archive.json");
+
+/// Sends every query once and compares the live responses with the recorded fixtures. Returns
+/// the number of queries checked, or an error naming the ones that drifted.
+pub(crate) async fn check_graphql_schema(state: &ServerState) -> anyhow::Result<usize> {
+    use crate::db::authors::dsl as dsla;
+    use crate::db::submissions::dsl;
+    let settings = &state.settings;
+    if settings.archive.replay {
+        tracing::info!("Replaying archived responses, not checking the live schema.");
+        return Ok(0);
+    }
+    let endpoint = settings.medium.graphql_endpoint.as_str();
+    let client = &state.http;
+    let mut connection = state.pool.get()?;
+
+    // Live checks need real ids, take the newest submission and any known author.
+    let submission = dsl::submissions
+        .order_by(dsl::latest_published_at.desc())
+        .select(Submission::as_select())
+        .first(&mut connection)
+        .optional()?;
+    let author_id = dsla::authors
+        .select(dsla::id)
+        .first::<String>(&mut connection)
+        .optional()?;
+
+    let mut checks = Vec::new();
+    if let Some(submission) = &submission {
+        checks.push(
+            check::<_, PostPageResult>(
+                client,
+                endpoint,
+                "PostPageQuery",
+                POST_PAGE_FIXTURE,
+                GraphQlRequest::from(PostPageQuery { post_id: &submission.guid }),
+            )
+            .await,
+        );
+        checks.push(
+            check::<_, ClapCountResult>(
+                client,
+                endpoint,
+                "ClapCountQuery",
+                CLAP_COUNT_FIXTURE,
+                GraphQlRequest::from(ClapCountQuery {
+                    post_id: &submission.guid,
+                    include_first_boosted_at: false,
+                }),
+            )
+            .await,
+        );
+    }
+    if let Some(author_id) = &author_id {
+        checks.push(
+            check::<_, UserProfileResult>(
+                client,
+                endpoint,
+                "UserProfileQuery",
+                USER_PROFILE_FIXTURE,
+                GraphQlRequest::from(UserProfileQuery { id: author_id }),
+            )
+            .await,
+        );
+    }
+    checks.push(
+        check::<_, TagArchiveFeedResult>(
+            client,
+            endpoint,
+            "TagArchiveFeedQuery",
+            TAG_ARCHIVE_FIXTURE,
+            GraphQlRequest::from(TagArchiveFeedQuery {
+                tag_slug: &settings.medium.tag_slug,
+                time_range: TimeRange { kind: "ALL_TIME" },
+                sort_order: "NEWEST",
+                first: 1,
+                after: None,
+            }),
+        )
+        .await,
+    );
+
+    let checked = checks.len();
+    let mut drifted = Vec::new();
+    for (operation, result) in checks {
+        match result {
+            Ok(()) => {
+                tracing::info!("{} matches the recorded fixture.", operation);
+                record_schema_drift(operation, false);
+            }
+            // Medium being unreachable, or the post being gone, says nothing about the schema.
+            Err(failure @ (IngestFailure::Network(_) | IngestFailure::RateLimited { .. } | IngestFailure::NotFound)) => {
+                tracing::warn!("Could not check {}: {}", operation, failure);
+            }
+            Err(failure) => {
+                tracing::error!("{} drifted from the recorded fixture: {}", operation, failure);
+                record_schema_drift(operation, true);
+                record_ingest_error(&mut connection, JOB_NAME, None, operation.to_owned(), &failure);
+                drifted.push(operation);
+            }
+        }
+    }
+
+    if drifted.is_empty() {
+        Ok(checked)
+    } else {
+        Err(anyhow!("GraphQL schema drifted for {}", drifted.join(", ")))
+    }
+}
+
+async fn check<V: Serialize, D: DeserializeOwned>(
+    client: &MediumClient,
+    endpoint: &str,
+    operation: &'static str,
+    fixture: &str,
+    request: GraphQlRequest<'_, V>,
+) -> (&'static str, Result<(), IngestFailure>) {
+    (operation, check_against_fixture::<V, D>(client, endpoint, fixture, request).await)
+}
+
+async fn check_against_fixture<V: Serialize, D: DeserializeOwned>(
+    client: &MediumClient,
+    endpoint: &str,
+    fixture: &str,
+    request: GraphQlRequest<'_, V>,
+) -> Result<(), IngestFailure> {
+    // A fixture our types cannot read is a bug in this code, not a change at Medium.
+    parse::<D>(fixture.as_bytes()).map_err(|err| {
+        IngestFailure::schema_changed(format!("Recorded fixture does not parse: {}", err))
+    })?;
+
+    let body = fetch_raw(client, endpoint, request).await?;
+    parse::<D>(&body)?;
+
+    let live = serde_json::from_slice::<Value>(&body)?;
+    // An unavailable post or user has none of the fields of a `Post` or `User`, there is nothing
+    // to compare.
+    if is_unavailable(&live) {
+        return Err(IngestFailure::NotFound);
+    }
+    let expected = shape(&serde_json::from_str::<Value>(fixture)?);
+    let live = shape(&live);
+    let differences = compare(&expected, &live);
+
+    if differences.is_empty() {
+        Ok(())
+    } else {
+        Err(IngestFailure::SchemaChanged {
+            error: differences.join("; "),
+            raw_response: Some(String::from_utf8_lossy(&body).into_owned()),
+        })
+    }
+}
+
+/// Whether the response holds a `postResult` that is not a `Post`, or a `userResult` that is not
+/// a `User`.
+fn is_unavailable(response: &Value) -> bool {
+    let data = &response[0]["data"];
+    let not = |union: &str, typename: &str| data[union]["__typename"].as_str().is_some_and(|found| found != typename);
+    not("postResult", "Post") || not("userResult", "User")
+}
+
+/// Flattens a JSON value into `path -> kind`, e.g. `[].data.postResult.clapCount -> number`.
+fn shape(value: &Value) -> BTreeMap<String, &'static str> {
+    fn walk(value: &Value, path: String, out: &mut BTreeMap<String, &'static str>) {
+        let kind = match value {
+            Value::Null => "null",
+            Value::Bool(_) => "bool",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(values) if values.is_empty() => "empty",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+        match value {
+            Value::Array(values) => values
+                .iter()
+                .for_each(|value| walk(value, format!("{}[]", path), out)),
+            Value::Object(fields) => fields
+                .iter()
+                .for_each(|(key, value)| walk(value, format!("{}.{}", path, key), out)),
+            _ => {}
+        }
+        out.insert(path, kind);
+    }
+
+    let mut out = BTreeMap::new();
+    walk(value, String::new(), &mut out);
+    out
+}
+
+/// Fields of the fixture that are missing or of another type in the live response. Fields below
+/// a `null` or an empty list in the live response are not comparable and skipped.
+fn compare(expected: &BTreeMap<String, &'static str>, live: &BTreeMap<String, &'static str>) -> Vec<String> {
+    let not_comparable = |path: &str| {
+        live.iter().any(|(prefix, kind)| {
+            (*kind == "null" || *kind == "empty")
+                && path.starts_with(prefix.as_str())
+                && path[prefix.len()..].starts_with(['.', '['])
+        })
+    };
+
+    expected
+        .iter()
+        .filter_map(|(path, kind)| match live.get(path) {
+            None if not_comparable(path) => None,
+            None => Some(format!("{} is missing", path)),
+            Some(live_kind) if live_kind == kind => None,
+            Some(&"null") => None,
+            Some(&"empty") if *kind == "array" => None,
+            Some(&"array") if *kind == "empty" => None,
+            Some(live_kind) => Some(format!("{} changed from {} to {}", path, kind, live_kind)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixtures_parse_into_their_response_types() {
+        parse::<PostPageResult>(POST_PAGE_FIXTURE.as_bytes()).unwrap();
+        parse::<ClapCountResult>(CLAP_COUNT_FIXTURE.as_bytes()).unwrap();
+        parse::<UserProfileResult>(USER_PROFILE_FIXTURE.as_bytes()).unwrap();
+        parse::<TagArchiveFeedResult>(TAG_ARCHIVE_FIXTURE.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn fixtures_match_themselves() {
+        for fixture in [POST_PAGE_FIXTURE, CLAP_COUNT_FIXTURE, USER_PROFILE_FIXTURE, TAG_ARCHIVE_FIXTURE] {
+            let shape = shape(&serde_json::from_str::<Value>(fixture).unwrap());
+            assert!(compare(&shape, &shape).is_empty());
+        }
+    }
+
+    #[test]
+    fn detects_unavailable_posts_and_users() {
+        let unavailable = serde_json::json!([{ "data": { "postResult": { "__typename": "PostUnavailable" } } }]);
+        assert!(is_unavailable(&unavailable));
+        let suspended = serde_json::json!([{ "data": { "userResult": { "__typename": "UserUnavailable" } } }]);
+        assert!(is_unavailable(&suspended));
+        assert!(!is_unavailable(&serde_json::from_str(POST_PAGE_FIXTURE).unwrap()));
+        assert!(!is_unavailable(&serde_json::from_str(USER_PROFILE_FIXTURE).unwrap()));
+        assert!(!is_unavailable(&serde_json::from_str(TAG_ARCHIVE_FIXTURE).unwrap()));
+    }
+
+    #[test]
+    fn unavailable_users_are_not_found() {
+        let suspended = br#"[{ "data": { "userResult": { "__typename": "UserUnavailable" } } }]"#;
+        let result = parse::<UserProfileResult>(suspended).unwrap();
+        assert!(matches!(result.into_user(), Err(IngestFailure::NotFound)));
+        let user = parse::<UserProfileResult>(USER_PROFILE_FIXTURE.as_bytes()).unwrap().into_user().unwrap();
+        assert_eq!(user.username, "janedoe");
+    }
+
+    #[test]
+    fn reports_missing_and_changed_fields() {
+        let expected = shape(&serde_json::json!({ "a": 1, "b": "x" }));
+        let live = shape(&serde_json::json!({ "b": 2 }));
+        assert_eq!(compare(&expected, &live), vec![".a is missing", ".b changed from string to number"]);
+    }
+}
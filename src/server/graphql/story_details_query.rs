@@ -1,7 +1,7 @@
 use serde::Deserialize;
 use serde::Serialize;
 
-use crate::server::graphql::{GraphQlRequest, GraphQlResponse};
+use crate::server::graphql::{GraphQlRequest, PostResult};
 
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -9,7 +9,7 @@ pub struct PostPageQuery<'a> {
     pub(crate) post_id: &'a str,
 }
 
-pub type PostPageResult = Vec<GraphQlResponse<PostResponse>>;
+pub type PostPageResult = PostResult<PostResponse>;
 
 impl<'a> From<PostPageQuery<'a>> for GraphQlRequest<'a, PostPageQuery<'a>> {
     fn from(variables: PostPageQuery<'a>) -> Self {
@@ -29,21 +29,30 @@ pub struct PostResponse {
     pub(crate) id: String,
     pub(crate) creator: CreatorResponse,
     #[allow(unused)]
-    pub(crate) medium_url: String,
-    pub(crate) latest_published_version: String,
-    pub(crate) latest_published_at: i64,
+    #[serde(default)]
+    pub(crate) medium_url: Option<String>,
+    #[serde(default)]
+    pub(crate) latest_published_version: Option<String>,
+    #[serde(default)]
+    pub(crate) latest_published_at: Option<i64>,
     pub(crate) clap_count: i32,
-    pub(crate) title: String,
-    pub(crate) preview_image: PreviewImageResponse,
-    pub(crate) tags: Vec<TagResponse>,
-    pub(crate) word_count: i32,
+    #[serde(default)]
+    pub(crate) title: Option<String>,
+    /// Missing on text-only posts.
+    #[serde(default)]
+    pub(crate) preview_image: Option<PreviewImageResponse>,
+    #[allow(unused)]
+    #[serde(default)]
+    pub(crate) tags: Option<Vec<TagResponse>>,
+    #[serde(default)]
+    pub(crate) word_count: Option<i32>,
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct CreatorResponse {
-    #[allow(unused)]
     pub(crate) id: String,
+    #[serde(default)]
     pub(crate) name: String,
     pub(crate) username: String,
 }
@@ -0,0 +1,73 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::server::graphql::{GraphQlRequest, TagResult};
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TagArchiveFeedQuery<'a> {
+    pub(crate) tag_slug: &'a str,
+    pub(crate) time_range: TimeRange,
+    pub(crate) sort_order: &'static str,
+    pub(crate) first: i32,
+    pub(crate) after: Option<&'a str>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TimeRange {
+    pub(crate) kind: &'static str,
+}
+
+pub type TagArchiveFeedResult = TagResult<TagArchiveResponse>;
+
+impl<'a> From<TagArchiveFeedQuery<'a>> for GraphQlRequest<'a, TagArchiveFeedQuery<'a>> {
+    fn from(variables: TagArchiveFeedQuery<'a>) -> Self {
+        Self {
+            operation_name: "TagArchiveFeedQuery",
+            query: TAG_ARCHIVE_FEED_QUERY,
+            variables,
+        }
+    }
+}
+
+const TAG_ARCHIVE_FEED_QUERY: &str = "query TagArchiveFeedQuery($tagSlug: String!, $timeRange: TagPostsTimeRange!, $sortOrder: TagPostsSortOrder!, $first: Int!, $after: String) {tagFromSlug(tagSlug: $tagSlug) {id\n sortedFeed: posts(timeRange: $timeRange, sortOrder: $sortOrder, first: $first, after: $after) {edges {cursor\n node {id\n firstPublishedAt\n collection {id\n slug\n __typename}\n __typename}\n __typename}\n pageInfo {hasNextPage\n endCursor\n __typename}\n __typename}\n __typename}}";
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TagArchiveResponse {
+    pub(crate) sorted_feed: PostConnectionResponse,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PostConnectionResponse {
+    pub(crate) edges: Vec<PostEdgeResponse>,
+    pub(crate) page_info: PageInfoResponse,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PostEdgeResponse {
+    pub(crate) node: ArchivePostResponse,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivePostResponse {
+    pub(crate) id: String,
+    pub(crate) first_published_at: i64,
+    pub(crate) collection: Option<CollectionResponse>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionResponse {
+    pub(crate) slug: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfoResponse {
+    pub(crate) has_next_page: bool,
+    pub(crate) end_cursor: Option<String>,
+}
@@ -0,0 +1,44 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::server::graphql::{GraphQlRequest, UserResult};
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserProfileQuery<'a> {
+    pub(crate) id: &'a str,
+}
+
+pub type UserProfileResult = UserResult<UserResponse>;
+
+impl<'a> From<UserProfileQuery<'a>> for GraphQlRequest<'a, UserProfileQuery<'a>> {
+    fn from(variables: UserProfileQuery<'a>) -> Self {
+        Self {
+            operation_name: "UserProfileQuery",
+            query: USER_PROFILE_QUERY,
+            variables,
+        }
+    }
+}
+
+const USER_PROFILE_QUERY: &str = "query UserProfileQuery($id: ID!) {userResult(id: $id) {__typename\n ... on User {id\n name\n username\n bio\n imageId\n socialStats {followerCount\n __typename}\n __typename}}}";
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserResponse {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) username: String,
+    #[serde(default)]
+    pub(crate) bio: Option<String>,
+    #[serde(default)]
+    pub(crate) image_id: Option<String>,
+    #[serde(default)]
+    pub(crate) social_stats: Option<SocialStatsResponse>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SocialStatsResponse {
+    pub(crate) follower_count: i32,
+}
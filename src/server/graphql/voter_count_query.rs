@@ -0,0 +1,30 @@
+use crate::server::graphql::{GraphQlRequest, PostResult};
+use serde::{Deserialize, Serialize};
+
+/// Only sent for contests scored by unique clappers.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VoterCountQuery<'a> {
+    pub(crate) post_id: &'a str,
+}
+
+pub type VoterCountResult = PostResult<VoterCountResponse>;
+
+impl<'a> From<VoterCountQuery<'a>> for GraphQlRequest<'a, VoterCountQuery<'a>> {
+    fn from(variables: VoterCountQuery<'a>) -> GraphQlRequest<'a, VoterCountQuery<'a>> {
+        Self {
+            operation_name: "VoterCountQuery",
+            query: VOTER_COUNT_QUERY,
+            variables,
+        }
+    }
+}
+
+const VOTER_COUNT_QUERY: &str = "query VoterCountQuery($postId: ID!) {\n  postResult(id: $postId) {\n    __typename\n    ... on Post {\n      id\n      voterCount\n      __typename\n    }\n  }\n}\n";
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VoterCountResponse {
+    #[serde(default)]
+    pub(crate) voter_count: Option<i32>,
+}
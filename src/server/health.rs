@@ -0,0 +1,48 @@
+use crate::db::{DbPool, MIGRATIONS};
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use diesel::RunQueryDsl;
+use diesel_migrations::MigrationHarness;
+use dioxus::logger::tracing;
+use metrics_exporter_prometheus::PrometheusHandle;
+
+/// `/healthz` answers as long as the process is up, `/readyz` only once the database is
+/// reachable and fully migrated. `/metrics` is the Prometheus scrape endpoint.
+pub fn routes(pool: DbPool, metrics: PrometheusHandle) -> Router {
+    Router::new()
+        .route("/healthz", get(|| async { "ok" }))
+        .route("/readyz", get(move || readyz(pool.clone())))
+        .route(
+            "/metrics",
+            get(move || {
+                let body = metrics.render();
+                async move { ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body) }
+            }),
+        )
+}
+
+async fn readyz(pool: DbPool) -> impl IntoResponse {
+    let check = tokio::task::spawn_blocking(move || check_database(&pool)).await;
+
+    match check {
+        Ok(Ok(())) => (StatusCode::OK, "ready".to_owned()),
+        Ok(Err(err)) => {
+            tracing::warn!("Readiness check failed: {}", err);
+            (StatusCode::SERVICE_UNAVAILABLE, err.to_string())
+        }
+        Err(err) => (StatusCode::SERVICE_UNAVAILABLE, err.to_string()),
+    }
+}
+
+fn check_database(pool: &DbPool) -> anyhow::Result<()> {
+    let mut connection = pool.get()?;
+    diesel::sql_query("SELECT 1").execute(&mut connection)?;
+
+    match connection.has_pending_migration(MIGRATIONS) {
+        Ok(false) => Ok(()),
+        Ok(true) => Err(anyhow::anyhow!("Migrations pending")),
+        Err(err) => Err(anyhow::anyhow!("Error checking migrations: {}", err)),
+    }
+}
@@ -0,0 +1,250 @@
+use crate::models::{
+    ClapRace, ClapRaceFrame, Contest, FrameEntry, ScoredSubmission, StandingsFrame, Submission, TimeTravel,
+};
+use crate::server::scoring;
+use crate::server::settings::Settings;
+use chrono::{DateTime, Local, TimeZone};
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use std::collections::HashMap;
+
+/// Recorded changes of the entries by guid, oldest first.
+type History = HashMap<String, Vec<(DateTime<Local>, i32)>>;
+
+fn load_history(connection: &mut PgConnection, entries: &[Submission]) -> anyhow::Result<History> {
+    use crate::db::clap_history::dsl as dslh;
+
+    let guids: Vec<&str> = entries.iter().map(|entry| entry.guid.as_str()).collect();
+    let mut history = History::new();
+    for (guid, timestamp, clap_count) in dslh::clap_history
+        .filter(dslh::guid.eq_any(&guids))
+        .order_by(dslh::timestamp)
+        .select((dslh::guid, dslh::timestamp, dslh::clap_count))
+        .load::<(String, DateTime<Local>, i32)>(connection)?
+    {
+        history.entry(guid).or_default().push((timestamp, clap_count));
+    }
+
+    Ok(history)
+}
+
+/// The clap count of `entry` at `at`.
+///
+/// `clap_history` only records changes, so the count an entry was ingested with is unknown.
+/// Until its first recorded change an entry counts with the earliest count known: the first
+/// recorded one, or the current one if its count never changed.
+fn clap_count_at(entry: &Submission, history: &History, at: DateTime<Local>) -> i32 {
+    match history.get(&entry.guid).map(Vec::as_slice) {
+        Some([(_, first), changes @ ..]) => changes
+            .iter()
+            .take_while(|(timestamp, _)| *timestamp <= at)
+            .last()
+            .map_or(*first, |(_, clap_count)| *clap_count),
+        _ => entry.clap_count,
+    }
+}
+
+/// When `entry` is first known to have been part of the contest: its first recorded change, or its
+/// publication if that is earlier. `latest_published_at` moves forward whenever a post is edited,
+/// so on its own it would hide edited entries from the moments before the edit.
+fn first_seen_at(entry: &Submission, history: &History) -> DateTime<Local> {
+    let published_at = Local
+        .timestamp_millis_opt(entry.latest_published_at)
+        .single()
+        .unwrap_or_else(Local::now);
+    match history.get(&entry.guid).and_then(|changes| changes.first()) {
+        Some((first_change, _)) => published_at.min(*first_change),
+        None => published_at,
+    }
+}
+
+/// When `entry`, with the clap count it had at `at`, first reached that count. Without a recorded
+/// change to it by `at`, the entry counts as having reached it when it was first seen.
+fn reached_count_at(entry: &Submission, history: &History, at: DateTime<Local>) -> DateTime<Local> {
+    history
+        .get(&entry.guid)
+        .and_then(|changes| {
+            changes
+                .iter()
+                .take_while(|(timestamp, _)| *timestamp <= at)
+                .find(|(_, clap_count)| *clap_count >= entry.clap_count)
+        })
+        .map_or_else(|| first_seen_at(entry, history), |(timestamp, _)| *timestamp)
+}
+
+/// Entries of the contest first seen by `at`, with their clap count then.
+fn entries_at(entries: &[Submission], history: &History, at: DateTime<Local>) -> Vec<Submission> {
+    entries
+        .iter()
+        .filter(|entry| first_seen_at(entry, history) <= at)
+        .map(|entry| Submission {
+            clap_count: clap_count_at(entry, history, at),
+            ..entry.clone()
+        })
+        .collect()
+}
+
+fn contest_entries(connection: &mut PgConnection, settings: &Settings, contest: &Contest) -> anyhow::Result<Vec<Submission>> {
+    use crate::db::submissions::dsl;
+
+    Ok(dsl::submissions
+        .filter(dsl::contest_id.eq(contest.id))
+        .filter(dsl::username.ne_all(&settings.leaderboard.excluded_usernames))
+        .select(Submission::as_select())
+        .load(connection)?)
+}
+
+/// `frames` evenly spaced moments from the start of the contest to its end or now.
+fn moments(contest: &Contest, frames: usize) -> impl Iterator<Item = DateTime<Local>> {
+    let start = contest.starts_at;
+    let end = contest.ends_at.map_or(Local::now(), |ends_at| ends_at.min(Local::now()));
+    let steps = frames.max(2) - 1;
+    (0..=steps).map(move |step| start + (end - start) * step as i32 / steps as i32)
+}
+
+/// The ranking of the contest as it stood at `at`, rebuilt from `clap_history`. Only entries
+/// seen by then are included, clap counts follow [`clap_count_at`].
+pub(crate) fn standings_at(
+    connection: &mut PgConnection,
+    settings: &Settings,
+    contest: &Contest,
+    at: DateTime<Local>,
+) -> anyhow::Result<Vec<ScoredSubmission>> {
+    let entries = contest_entries(connection, settings, contest)?;
+    let history = load_history(connection, &entries)?;
+    let judge_scores = scoring::judge_scores(connection, settings, contest)?;
+
+    scoring::rank_with_judges(settings, contest, entries_at(&entries, &history, at), judge_scores, at, |entry| {
+        Ok(reached_count_at(entry, &history, at))
+    })
+}
+
+/// The ranking of the contest at `frames` evenly spaced moments, from its start to its end or
+/// now, so the time travel page can play the race back from a single request.
+pub(crate) fn time_travel(
+    connection: &mut PgConnection,
+    settings: &Settings,
+    contest: &Contest,
+    frames: usize,
+) -> anyhow::Result<TimeTravel> {
+    let submissions = contest_entries(connection, settings, contest)?;
+    let history = load_history(connection, &submissions)?;
+    let judge_scores = scoring::judge_scores(connection, settings, contest)?;
+
+    let frames = moments(contest, frames)
+        .map(|at| {
+            let entries = entries_at(&submissions, &history, at);
+            let ranked = scoring::rank_with_judges(settings, contest, entries, judge_scores.clone(), at, |entry| {
+                Ok(reached_count_at(entry, &history, at))
+            })?;
+            Ok(StandingsFrame {
+                at,
+                entries: ranked
+                    .into_iter()
+                    .map(|entry| FrameEntry {
+                        guid: entry.submission.guid,
+                        clap_count: entry.submission.clap_count,
+                        score: entry.score,
+                        breakdown: entry.breakdown,
+                        rank: entry.rank,
+                        tie_break: entry.tie_break,
+                    })
+                    .collect(),
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(TimeTravel { submissions, frames })
+}
+
+/// The clap counts of all entries of the contest at `frames` evenly spaced moments, from its
+/// start to its end or now. Counts follow [`clap_count_at`].
+pub(crate) fn clap_race(
+    connection: &mut PgConnection,
+    settings: &Settings,
+    contest: &Contest,
+    frames: usize,
+) -> anyhow::Result<ClapRace> {
+    let submissions = contest_entries(connection, settings, contest)?;
+    let history = load_history(connection, &submissions)?;
+
+    let frames = moments(contest, frames)
+        .map(|at| ClapRaceFrame {
+            at,
+            clap_counts: entries_at(&submissions, &history, at)
+                .into_iter()
+                .map(|entry| (entry.guid, entry.clap_count))
+                .collect(),
+        })
+        .collect();
+
+    Ok(ClapRace { submissions, frames })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(day: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2025, 1, day, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn counts_before_the_first_change_are_the_earliest_known() {
+        let changed = Submission {
+            guid: "changed".to_owned(),
+            clap_count: 30,
+            ..Default::default()
+        };
+        let unchanged = Submission {
+            guid: "unchanged".to_owned(),
+            clap_count: 12,
+            ..Default::default()
+        };
+        let history = History::from([("changed".to_owned(), vec![(day(10), 5), (day(12), 20), (day(14), 30)])]);
+
+        assert_eq!(clap_count_at(&changed, &history, day(1)), 5);
+        assert_eq!(clap_count_at(&changed, &history, day(11)), 5);
+        assert_eq!(clap_count_at(&changed, &history, day(13)), 20);
+        assert_eq!(clap_count_at(&changed, &history, day(20)), 30);
+        assert_eq!(clap_count_at(&unchanged, &history, day(1)), 12);
+    }
+
+    #[test]
+    fn edited_entries_stay_in_the_moments_before_the_edit() {
+        let edited = Submission {
+            guid: "edited".to_owned(),
+            clap_count: 30,
+            latest_published_at: day(15).timestamp_millis(),
+            ..Default::default()
+        };
+        let history = History::from([("edited".to_owned(), vec![(day(10), 5), (day(12), 20), (day(16), 30)])]);
+
+        assert!(entries_at(std::slice::from_ref(&edited), &history, day(9)).is_empty());
+        let then = entries_at(std::slice::from_ref(&edited), &history, day(13));
+        assert_eq!(then.len(), 1);
+        assert_eq!(then[0].clap_count, 20);
+    }
+
+    #[test]
+    fn ties_are_broken_by_the_history_up_to_the_moment() {
+        let entry = Submission {
+            guid: "entry".to_owned(),
+            clap_count: 20,
+            latest_published_at: day(1).timestamp_millis(),
+            ..Default::default()
+        };
+        let history = History::from([("entry".to_owned(), vec![(day(10), 5), (day(12), 20), (day(14), 20)])]);
+
+        assert_eq!(reached_count_at(&entry, &history, day(13)), day(12));
+        let earlier = Submission {
+            clap_count: clap_count_at(&entry, &history, day(11)),
+            ..entry.clone()
+        };
+        assert_eq!(reached_count_at(&earlier, &history, day(11)), day(10));
+        let before_history = Submission {
+            clap_count: 5,
+            ..entry.clone()
+        };
+        assert_eq!(reached_count_at(&before_history, &history, day(5)), day(1));
+    }
+}
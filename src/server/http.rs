@@ -0,0 +1,208 @@
+use crate::server::failure::{retry_after, IngestFailure};
+use crate::server::settings::HttpSettings;
+use dioxus::logger::tracing;
+use reqwest::{header, StatusCode};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Longest a single backoff waits, regardless of `Retry-After`.
+const MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
+/// Most retries `max_retries` may allow, beyond that a request would be held back for hours.
+pub(crate) const MAX_RETRIES: u32 = 10;
+
+/// The one client used for every request to Medium. Shares connections, spaces requests out with
+/// a token bucket and backs off when Medium asks us to.
+#[derive(Clone)]
+pub struct MediumClient {
+    client: reqwest::Client,
+    limiter: Arc<TokenBucket>,
+    max_retries: u32,
+    /// `ETag` and `Last-Modified` of the last feed response, per url.
+    validators: Arc<Mutex<HashMap<String, Validators>>>,
+}
+
+#[derive(Clone, Default)]
+struct Validators {
+    etag: Option<header::HeaderValue>,
+    last_modified: Option<header::HeaderValue>,
+}
+
+impl MediumClient {
+    pub fn new(settings: &HttpSettings) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .user_agent(&settings.user_agent)
+            .connect_timeout(Duration::from_secs(settings.connect_timeout_seconds))
+            .timeout(Duration::from_secs(settings.request_timeout_seconds))
+            .pool_idle_timeout(Duration::from_secs(90))
+            .build()?;
+
+        Ok(Self {
+            client,
+            limiter: Arc::new(TokenBucket::new(settings.burst, settings.requests_per_second)),
+            max_retries: settings.max_retries,
+            validators: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    pub(crate) async fn post_json<T: Serialize + ?Sized>(
+        &self,
+        url: &str,
+        body: &T,
+    ) -> Result<reqwest::Response, IngestFailure> {
+        self.send(|| self.client.post(url).json(body)).await
+    }
+
+    /// Conditional GET of a feed. `None` if it did not change since the last request.
+    pub(crate) async fn get_feed(&self, url: &str) -> Result<Option<Vec<u8>>, IngestFailure> {
+        let validators = self
+            .validators
+            .lock()
+            .expect("Feed validators lock poisoned")
+            .get(url)
+            .cloned()
+            .unwrap_or_default();
+
+        let response = self
+            .send(|| {
+                let mut request = self.client.get(url);
+                if let Some(etag) = &validators.etag {
+                    request = request.header(header::IF_NONE_MATCH, etag.clone());
+                }
+                if let Some(last_modified) = &validators.last_modified {
+                    request = request.header(header::IF_MODIFIED_SINCE, last_modified.clone());
+                }
+                request
+            })
+            .await?;
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            tracing::info!("Feed {} not modified.", url);
+            return Ok(None);
+        }
+        if let Some(failure) = IngestFailure::from_response(&response) {
+            return Err(failure);
+        }
+
+        let validators = Validators {
+            etag: response.headers().get(header::ETAG).cloned(),
+            last_modified: response.headers().get(header::LAST_MODIFIED).cloned(),
+        };
+        let body = response.bytes().await?.to_vec();
+        self.validators
+            .lock()
+            .expect("Feed validators lock poisoned")
+            .insert(url.to_owned(), validators);
+
+        Ok(Some(body))
+    }
+
+    /// Sends the request built by `request`, waiting for the rate limiter first. Retries on
+    /// 429 and 503, honoring `Retry-After`, until `max_retries` is used up.
+    async fn send(
+        &self,
+        request: impl Fn() -> reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, IngestFailure> {
+        let mut attempt = 0;
+        loop {
+            self.limiter.acquire().await;
+            let response = request().send().await?;
+
+            let status = response.status();
+            if status != StatusCode::TOO_MANY_REQUESTS && status != StatusCode::SERVICE_UNAVAILABLE {
+                return Ok(response);
+            }
+            if attempt >= self.max_retries {
+                return Ok(response);
+            }
+
+            let delay = retry_after(&response)
+                .unwrap_or_else(|| backoff(attempt))
+                .min(MAX_BACKOFF);
+            tracing::warn!("Medium answered {}, backing off for {:?}.", status, delay);
+            // Other requests would only run into the same limit, hold them back as well.
+            self.limiter.pause(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// The wait before retry `attempt` without a `Retry-After`: 2 seconds, doubling with every
+/// attempt, up to [`MAX_BACKOFF`].
+fn backoff(attempt: u32) -> Duration {
+    2u32.checked_pow(attempt)
+        .and_then(|factor| Duration::from_secs(2).checked_mul(factor))
+        .map_or(MAX_BACKOFF, |delay| delay.min(MAX_BACKOFF))
+}
+
+/// Allows `capacity` requests at once and refills at `rate` requests per second.
+struct TokenBucket {
+    capacity: f64,
+    rate: f64,
+    state: tokio::sync::Mutex<BucketState>,
+}
+
+struct BucketState {
+    tokens: f64,
+    refilled_at: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl TokenBucket {
+    fn new(capacity: u32, rate: f64) -> Self {
+        Self {
+            capacity: capacity.max(1) as f64,
+            rate,
+            state: tokio::sync::Mutex::new(BucketState {
+                tokens: capacity.max(1) as f64,
+                refilled_at: Instant::now(),
+                paused_until: None,
+            }),
+        }
+    }
+
+    async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().await;
+                let now = Instant::now();
+                let elapsed = now.duration_since(state.refilled_at).as_secs_f64();
+                state.tokens = (state.tokens + elapsed * self.rate).min(self.capacity);
+                state.refilled_at = now;
+
+                match state.paused_until {
+                    Some(paused_until) if paused_until > now => paused_until - now,
+                    _ if state.tokens >= 1.0 => {
+                        state.tokens -= 1.0;
+                        return;
+                    }
+                    _ => Duration::from_secs_f64((1.0 - state.tokens) / self.rate),
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    async fn pause(&self, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut state = self.state.lock().await;
+        state.paused_until = Some(state.paused_until.map_or(until, |paused| paused.max(until)));
+        state.tokens = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        assert_eq!(backoff(0), Duration::from_secs(2));
+        assert_eq!(backoff(3), Duration::from_secs(16));
+        assert_eq!(backoff(10), MAX_BACKOFF);
+        assert_eq!(backoff(40), MAX_BACKOFF);
+        assert_eq!(backoff(u32::MAX), MAX_BACKOFF);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::models::{JudgeScore, JuryCriterion, JuryResult};
+use crate::server::settings::Settings;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use std::collections::{BTreeMap, HashMap};
+
+/// A judge's weighted total from 0 to 100, over the criteria the judge scored. Scores of
+/// criteria that were removed from the settings are ignored.
+fn judge_total(criteria: &[JuryCriterion], scores: &[&JudgeScore]) -> Option<f64> {
+    let mut total = 0.0;
+    let mut weights = 0.0;
+    for score in scores {
+        if let Some(criterion) = criteria.iter().find(|criterion| criterion.name == score.criterion) {
+            total += criterion.weight * score.score as f64 / criterion.max as f64;
+            weights += criterion.weight;
+        }
+    }
+
+    (weights > 0.0).then(|| total * 100.0 / weights)
+}
+
+/// All scores given for entries of the contest.
+pub(crate) fn contest_scores(connection: &mut PgConnection, contest_id: i32) -> anyhow::Result<Vec<JudgeScore>> {
+    use crate::db::judge_scores::dsl;
+    use crate::db::submissions::dsl as dsls;
+
+    Ok(dsl::judge_scores
+        .inner_join(dsls::submissions)
+        .filter(dsls::contest_id.eq(contest_id))
+        .order_by((dsl::guid.asc(), dsl::judge.asc(), dsl::criterion.asc()))
+        .select(JudgeScore::as_select())
+        .load(connection)?)
+}
+
+/// The jury result of every scored entry of the contest, by guid.
+pub(crate) fn results(
+    connection: &mut PgConnection,
+    settings: &Settings,
+    contest_id: i32,
+) -> anyhow::Result<HashMap<String, JuryResult>> {
+    let scores = contest_scores(connection, contest_id)?;
+
+    let mut by_guid: BTreeMap<&str, BTreeMap<&str, Vec<&JudgeScore>>> = BTreeMap::new();
+    for score in &scores {
+        by_guid
+            .entry(&score.guid)
+            .or_default()
+            .entry(&score.judge)
+            .or_default()
+            .push(score);
+    }
+
+    let mut results = HashMap::new();
+    for (guid, by_judge) in by_guid {
+        let totals: Vec<f64> = by_judge
+            .values()
+            .filter_map(|scores| judge_total(&settings.jury.criteria, scores))
+            .collect();
+        if totals.is_empty() {
+            continue;
+        }
+
+        let criteria = settings
+            .jury
+            .criteria
+            .iter()
+            .filter_map(|criterion| {
+                let given: Vec<i32> = by_judge
+                    .values()
+                    .flatten()
+                    .filter(|score| score.criterion == criterion.name)
+                    .map(|score| score.score)
+                    .collect();
+                (!given.is_empty())
+                    .then(|| (criterion.name.clone(), given.iter().sum::<i32>() as f64 / given.len() as f64))
+            })
+            .collect();
+
+        results.insert(
+            guid.to_owned(),
+            JuryResult {
+                guid: guid.to_owned(),
+                judges: totals.len(),
+                score: totals.iter().sum::<f64>() / totals.len() as f64,
+                criteria,
+            },
+        );
+    }
+
+    Ok(results)
+}
@@ -0,0 +1,121 @@
+use crate::db::DbPool;
+use diesel::sql_types::{Integer, Text};
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use dioxus::logger::tracing;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// Leases not renewed within this time are considered abandoned, e.g. by a crashed instance.
+const LEASE_TTL: Duration = Duration::from_secs(5 * 60);
+const LEASE_RENEWAL: Duration = Duration::from_secs(60);
+
+/// Identifies this process as lease holder across replicas.
+pub fn instance_id() -> &'static str {
+    static INSTANCE_ID: OnceLock<String> = OnceLock::new();
+    INSTANCE_ID.get_or_init(|| {
+        format!(
+            "{}-{}-{}",
+            std::env::var("HOSTNAME").unwrap_or_else(|_| "unknown".to_owned()),
+            std::process::id(),
+            chrono::Utc::now().timestamp_millis()
+        )
+    })
+}
+
+/// Exclusive right of this instance to run a job. Renewed in the background until released.
+pub struct Lease {
+    pool: DbPool,
+    job_name: &'static str,
+    renewal: JoinHandle<()>,
+    lost: watch::Receiver<bool>,
+}
+
+impl Lease {
+    /// Takes the lease for `job_name` if it is free, expired or already ours.
+    pub fn try_acquire(pool: &DbPool, job_name: &'static str) -> anyhow::Result<Option<Lease>> {
+        if !claim(pool, job_name)? {
+            return Ok(None);
+        }
+
+        let (lost_sender, lost) = watch::channel(false);
+        let renewal = {
+            let pool = pool.clone();
+            tokio::spawn(async move {
+                let mut timer = tokio::time::interval(LEASE_RENEWAL);
+                timer.tick().await;
+                loop {
+                    timer.tick().await;
+                    let pool = pool.clone();
+                    match tokio::task::spawn_blocking(move || claim(&pool, job_name)).await {
+                        Ok(Ok(true)) => {}
+                        Ok(Ok(false)) => {
+                            tracing::error!("Lost lease for job {}.", job_name);
+                            let _ = lost_sender.send(true);
+                            return;
+                        }
+                        Ok(Err(err)) => tracing::error!("Error renewing lease for job {}: {}", job_name, err),
+                        Err(err) => tracing::error!("Error renewing lease for job {}: {}", job_name, err),
+                    }
+                }
+            })
+        };
+
+        Ok(Some(Lease {
+            pool: pool.clone(),
+            job_name,
+            renewal,
+            lost,
+        }))
+    }
+
+    /// Completes once another instance has taken over the lease. The job has to stop then.
+    pub async fn lost(&self) {
+        let mut lost = self.lost.clone();
+        if lost.wait_for(|lost| *lost).await.is_err() {
+            // The renewal ended without losing the lease, which only happens on release.
+            std::future::pending::<()>().await;
+        }
+    }
+
+    pub fn release(self) {
+        use crate::db::job_leases::dsl;
+        self.renewal.abort();
+
+        let released = self.pool.get().map_err(anyhow::Error::from).and_then(|mut connection| {
+            Ok(diesel::delete(
+                dsl::job_leases
+                    .filter(dsl::job_name.eq(self.job_name))
+                    .filter(dsl::holder.eq(instance_id())),
+            )
+            .execute(&mut connection)?)
+        });
+
+        if let Err(err) = released {
+            tracing::error!("Error releasing lease for job {}: {}", self.job_name, err);
+        }
+    }
+}
+
+/// Inserts or extends the lease row. Uses the database clock so replicas agree on expiry.
+fn claim(pool: &DbPool, job_name: &str) -> anyhow::Result<bool> {
+    let mut connection = pool.get()?;
+
+    let affected = diesel::sql_query(
+        "INSERT INTO job_leases (job_name, holder, acquired_at, expires_at) \
+         VALUES ($1, $2, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP + make_interval(secs => $3)) \
+         ON CONFLICT (job_name) DO UPDATE \
+         SET holder = EXCLUDED.holder, \
+             acquired_at = CASE WHEN job_leases.holder = EXCLUDED.holder \
+                                THEN job_leases.acquired_at ELSE EXCLUDED.acquired_at END, \
+             expires_at = EXCLUDED.expires_at \
+         WHERE job_leases.holder = EXCLUDED.holder OR job_leases.expires_at < CURRENT_TIMESTAMP",
+    )
+    .bind::<Text, _>(job_name)
+    .bind::<Text, _>(instance_id())
+    .bind::<Integer, _>(LEASE_TTL.as_secs() as i32)
+    .execute(&mut connection)?;
+
+    Ok(affected == 1)
+}
@@ -1,39 +1,294 @@
+mod archive;
+pub mod auth;
+mod backfill;
+pub mod cards;
+pub mod email;
+pub mod embed;
+pub mod failure;
+pub mod feeds;
 mod graphql;
+pub mod health;
+pub mod history;
+pub mod http;
+pub mod jury;
+mod lease;
+pub mod notifications;
+pub mod scheduler;
+pub mod scoring;
+pub mod settings;
+pub mod sitemap;
+pub mod telemetry;
+pub mod voting;
 
 use crate::db::DbPool;
-use crate::models::{InsertClapHistory, InsertSubmission, Submission};
+use crate::models::{
+    Contest, InsertAuthor, InsertClapHistory, InsertIngestError, InsertSubmission, Submission,
+};
+use crate::server::failure::IngestFailure;
 use crate::server::graphql::clap_count_query::{ClapCountQuery, ClapCountResult};
 use crate::server::graphql::story_details_query::{PostPageQuery, PostPageResult};
-use crate::server::graphql::{GraphQlRequest, GRAPHQL_ENDPOINT};
-use anyhow::anyhow;
-use chrono::TimeDelta;
+use crate::server::graphql::user_profile_query::{UserProfileQuery, UserProfileResult};
+use crate::server::graphql::voter_count_query::{VoterCountQuery, VoterCountResult};
+use crate::server::graphql::{self_check, GraphQlRequest};
+use crate::server::http::MediumClient;
+use crate::server::notifications::Notification;
+use crate::server::scheduler::{Job, JobRegistry};
+use crate::server::settings::{ContestSettings, Settings};
+use crate::server::telemetry::{record_clap_update, record_submissions_ingested, track_medium_request};
 use diesel::SelectableHelper;
-use diesel::{associations::HasTable, ExpressionMethods, Insertable, QueryDsl, RunQueryDsl};
+use diesel::{
+    associations::HasTable, ExpressionMethods, Insertable, NullableExpressionMethods, PgConnection,
+    QueryDsl, RunQueryDsl,
+};
 use dioxus::logger::tracing;
 use reqwest::{Method, Request};
 use rss::Channel;
-use std::time::Duration;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::sync::Arc;
 
-async fn update_rss(pool: &DbPool) -> anyhow::Result<()> {
+/// Shared by the scheduled jobs and the server functions.
+#[derive(Clone)]
+pub struct ServerState {
+    pub pool: DbPool,
+    pub settings: Arc<Settings>,
+    pub http: MediumClient,
+}
+
+/// Accepts a Medium post id, a story URL (`.../some-title-1a2b3c4d5e6f`) or a short link
+/// (`https://medium.com/p/1a2b3c4d5e6f`) and returns the post id.
+pub fn extract_post_id(input: &str) -> Option<String> {
+    let path = input.trim().split(['?', '#']).next()?;
+    let segment = path.split('/').rfind(|segment| !segment.is_empty())?;
+    let post_id = segment.rsplit('-').next()?;
+
+    if (8..=16).contains(&post_id.len()) && post_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(post_id.to_ascii_lowercase())
+    } else {
+        None
+    }
+}
+
+pub(crate) fn current_contest(connection: &mut PgConnection, settings: &Settings) -> anyhow::Result<Contest> {
+    use crate::db::contests::dsl;
+
+    Ok(dsl::contests
+        .filter(dsl::slug.eq(&settings.contest.slug))
+        .select(Contest::as_select())
+        .first(connection)?)
+}
+
+/// Creates the configured contest or updates it to match the settings.
+pub fn sync_contest(pool: &DbPool, contest: &ContestSettings) -> anyhow::Result<()> {
+    use crate::db::contests::dsl;
+    let mut connection = pool.get()?;
+
+    diesel::insert_into(dsl::contests)
+        .values((
+            dsl::slug.eq(&contest.slug),
+            dsl::title.eq(&contest.title),
+            dsl::publication.eq(&contest.publication),
+            dsl::starts_at.eq(contest.starts_at.unwrap_or_else(chrono::Utc::now)),
+            dsl::ends_at.eq(contest.ends_at),
+        ))
+        .on_conflict(dsl::slug)
+        .do_update()
+        .set((
+            dsl::title.eq(&contest.title),
+            dsl::publication.eq(&contest.publication),
+        ))
+        .execute(&mut connection)?;
+
+    if let Some(starts_at) = contest.starts_at {
+        diesel::update(dsl::contests.filter(dsl::slug.eq(&contest.slug)))
+            .set(dsl::starts_at.eq(starts_at))
+            .execute(&mut connection)?;
+    }
+    if let Some(ends_at) = contest.ends_at {
+        diesel::update(dsl::contests.filter(dsl::slug.eq(&contest.slug)))
+            .set(dsl::ends_at.eq(ends_at))
+            .execute(&mut connection)?;
+    }
+    // Without `scoring` in the settings, the strategy picked on the admin page stays.
+    if let Some(scoring) = &contest.scoring {
+        diesel::update(dsl::contests.filter(dsl::slug.eq(&contest.slug)))
+            .set(dsl::scoring.eq(serde_json::to_string(scoring)?))
+            .execute(&mut connection)?;
+    }
+
+    Ok(())
+}
+
+/// Stores the author profile, then the submission. A failing profile fetch does not block the
+/// submission, the author is linked later by `update_story_details`.
+pub(crate) async fn insert_submission(
+    connection: &mut PgConnection,
+    state: &ServerState,
+    mut new_submission: InsertSubmission,
+) -> Result<usize, IngestFailure> {
+    use crate::db::submissions::dsl;
+
+    if let Some(author_id) = &new_submission.author_id {
+        if let Err(err) = update_author(connection, state, author_id).await {
+            tracing::warn!("Could not store author {}: {}", author_id, err);
+            new_submission.author_id = None;
+        }
+    }
+
+    let rows_affected = (&new_submission)
+        .insert_into(dsl::submissions::table())
+        .execute(connection)?;
+
+    if rows_affected != 1 {
+        tracing::warn!("Insertion of submission failed: {}", new_submission.guid);
+    }
+    record_submissions_ingested(rows_affected);
+
+    Ok(rows_affected)
+}
+
+/// Fetches and stores a single post, recording the outcome in `ingest_items`. Failures are also
+/// logged to `ingest_errors`. `category` overrides the one derived from the post.
+pub(crate) async fn ingest_post(
+    connection: &mut PgConnection,
+    state: &ServerState,
+    job_name: &str,
+    contest_id: i32,
+    guid: &str,
+    category: Option<crate::models::Category>,
+    detail: String,
+) -> Result<usize, IngestFailure> {
+    let result = match fetch_story_details(state, guid).await {
+        Ok(details) => {
+            insert_submission(
+                connection,
+                state,
+                InsertSubmission {
+                    contest_id,
+                    category: category.unwrap_or(details.category),
+                    ..details
+                },
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
+
+    let marked = match &result {
+        Ok(_) => failure::mark_ingested(connection, job_name, guid),
+        Err(err) => {
+            record_ingest_error(connection, job_name, Some(guid), detail, err);
+            failure::mark_failed(connection, job_name, guid, err).map(|status| {
+                tracing::warn!("Ingesting {} failed ({:?}): {}", guid, status, err);
+            })
+        }
+    };
+    if let Err(err) = marked {
+        tracing::error!("Error recording ingest status of {}: {}", guid, err);
+    }
+
+    result
+}
+
+/// Adds a post picked on the admin page to the current contest, the same way the feed does.
+pub(crate) async fn add_post(
+    state: &ServerState,
+    guid: &str,
+    category: crate::models::Category,
+) -> anyhow::Result<()> {
+    use crate::db::submissions::dsl;
+    let mut connection = state.pool.get()?;
+    let contest = current_contest(&mut connection, &state.settings)?;
+
+    if dsl::submissions
+        .find(guid)
+        .count()
+        .get_result::<i64>(&mut connection)?
+        > 0
+    {
+        anyhow::bail!("Submission {} is already present.", guid);
+    }
+
+    let rows = ingest_post(
+        &mut connection,
+        state,
+        "add_submission",
+        contest.id,
+        guid,
+        Some(category),
+        String::new(),
+    )
+    .await?;
+    if rows > 0 {
+        notifications::announce_submission(&mut connection, &state.settings, &contest, guid);
+    }
+
+    Ok(())
+}
+
+/// Retries posts that failed with a transient error and are due again.
+async fn retry_failed_posts(
+    connection: &mut PgConnection,
+    state: &ServerState,
+    contest_id: i32,
+) -> anyhow::Result<usize> {
+    use crate::db::submissions::dsl;
+    let mut inserted = 0;
+
+    for item in failure::due_retries(connection)? {
+        if dsl::submissions
+            .find(&item.guid)
+            .count()
+            .get_result::<i64>(connection)?
+            > 0
+        {
+            failure::mark_ingested(connection, &item.job_name, &item.guid)?;
+            continue;
+        }
+
+        tracing::info!("Retrying {} (attempt {}).", item.guid, item.attempts + 1);
+        match ingest_post(connection, state, &item.job_name, contest_id, &item.guid, None, String::new()).await {
+            Ok(rows) => inserted += rows,
+            Err(IngestFailure::RateLimited { .. }) => break,
+            Err(_) => continue,
+        }
+    }
+
+    Ok(inserted)
+}
+
+async fn update_rss(state: &ServerState) -> anyhow::Result<usize> {
     use crate::schema::submissions::dsl;
+    let settings = &state.settings;
     tracing::info!("Fetching rss feed.");
 
-    let response = reqwest::get("https://medium.com/feed/my-fair-lighthouse/tagged/mfl-contest")
-        .await?
-        .bytes()
-        .await?;
-    let channel = Channel::read_from(&response[..])?;
-    let mut connection = pool.get()?;
+    let channel = fetch_feed(state).await?;
+    let mut connection = state.pool.get()?;
+    let mut inserted = 0;
+
+    let contest = current_contest(&mut connection, settings)?;
+    let items = channel.map(|channel| channel.items).unwrap_or_default();
 
-    for item in channel.items {
+    for item in items {
         let guid = item
             .guid
             .as_ref()
-            .and_then(|uri| uri.value.split('/').last().map(|guid| guid.to_owned()));
+            .and_then(|uri| extract_post_id(&uri.value));
 
         match guid {
             None => {
                 tracing::warn!("Could not extract guid from rss item: {:?}", item);
+                record_ingest_error(
+                    &mut connection,
+                    "update_rss",
+                    None,
+                    format!(
+                        "{} ({})",
+                        item.title.as_deref().unwrap_or("untitled"),
+                        item.link.as_deref().unwrap_or("no link")
+                    ),
+                    &IngestFailure::schema_changed("Could not extract guid from rss item"),
+                );
                 continue;
             }
             Some(guid) => {
@@ -46,116 +301,255 @@ async fn update_rss(pool: &DbPool) -> anyhow::Result<()> {
                     tracing::info!("Submission for guid {} already present in db.", guid);
                     continue;
                 }
-                let new_submission = fetch_story_details(&guid).await?;
-
-                let rows_affected = new_submission
-                    .insert_into(dsl::submissions::table())
-                    .execute(&mut connection)?;
-
-                if rows_affected != 1 {
-                    tracing::warn!("Insertion of submission failed: {}", guid);
+                if !failure::should_attempt(&mut connection, &guid)? {
+                    tracing::info!("Skipping {}, it failed before and is not due for a retry.", guid);
+                    continue;
+                }
+                let title = item.title.clone().unwrap_or_default();
+                match ingest_post(&mut connection, state, "update_rss", contest.id, &guid, None, title).await {
+                    Ok(rows) => {
+                        inserted += rows;
+                        if rows > 0 {
+                            notifications::announce_submission(&mut connection, settings, &contest, &guid);
+                        }
+                    }
+                    Err(IngestFailure::RateLimited { .. }) => {
+                        tracing::warn!("Rate limited by Medium, stopping the feed update.");
+                        return Ok(inserted);
+                    }
+                    Err(_) => continue,
                 }
             }
         };
     }
 
-    Ok(())
+    inserted += retry_failed_posts(&mut connection, state, contest.id).await?;
+
+    Ok(inserted)
 }
 
-async fn update_story_details(_pool: &DbPool) -> anyhow::Result<()> {
+async fn update_story_details(state: &ServerState) -> anyhow::Result<usize> {
+    use crate::db::submissions::dsl;
     tracing::info!("Updating all story details.");
+    let mut connection = state.pool.get()?;
+    let mut updated = 0;
+
+    let without_author = dsl::submissions
+        .filter(dsl::author_id.is_null())
+        .select(Submission::as_select())
+        .load(&mut connection)?;
+
+    for submission in without_author {
+        let details = match fetch_story_details(state, &submission.guid).await {
+            Ok(details) => details,
+            Err(err) => {
+                tracing::warn!("Could not fetch details for {}: {}", submission.guid, err);
+                continue;
+            }
+        };
+        let Some(author_id) = details.author_id else {
+            continue;
+        };
+        if let Err(err) = update_author(&mut connection, state, &author_id).await {
+            tracing::warn!("Could not store author {}: {}", author_id, err);
+            continue;
+        }
+        diesel::update(&submission)
+            .set((
+                dsl::author_id.eq(&author_id),
+                dsl::details_last_updated_at.eq(chrono::Local::now()),
+            ))
+            .execute(&mut connection)?;
+        tracing::info!("Linked {} to author {}.", submission.guid, author_id);
+        updated += 1;
+    }
+
+    let author_ids = dsl::submissions
+        .filter(dsl::author_id.is_not_null())
+        .select(dsl::author_id.assume_not_null())
+        .distinct()
+        .load::<String>(&mut connection)?;
+
+    for author_id in author_ids {
+        match update_author(&mut connection, state, &author_id).await {
+            Ok(()) => updated += 1,
+            Err(err) => tracing::warn!("Could not refresh author {}: {}", author_id, err),
+        }
+    }
+
+    Ok(updated)
+}
+
+/// Sends a GraphQL query, or answers it from the archive in replay mode. `kind` names the query in
+/// metrics and in the archive, `request_key` identifies the requested object.
+pub(crate) async fn fetch_graphql<V: Serialize, D: DeserializeOwned>(
+    state: &ServerState,
+    kind: &'static str,
+    request_key: &str,
+    request: GraphQlRequest<'_, V>,
+) -> Result<D, IngestFailure> {
+    let body = if state.settings.archive.replay {
+        archive::load(state, kind, request_key)?
+    } else {
+        let body = track_medium_request(
+            kind,
+            graphql::fetch_raw(&state.http, &state.settings.medium.graphql_endpoint, request),
+        )
+        .await?;
+        archive::store(state, kind, request_key, &body);
+        body
+    };
+
+    graphql::parse(&body)
+}
+
+/// The feed, or `None` if it did not change since the last fetch.
+async fn fetch_feed(state: &ServerState) -> Result<Option<Channel>, IngestFailure> {
+    let feed_url = &state.settings.medium.feed_url;
+
+    let body = if state.settings.archive.replay {
+        archive::load(state, "rss_feed", feed_url)?
+    } else {
+        let Some(body) = track_medium_request("rss_feed", state.http.get_feed(feed_url)).await? else {
+            return Ok(None);
+        };
+        archive::store(state, "rss_feed", feed_url, &body);
+        body
+    };
+
+    Ok(Some(Channel::read_from(&body[..])?))
+}
+
+async fn fetch_author_profile(state: &ServerState, author_id: &str) -> Result<InsertAuthor, IngestFailure> {
+    tracing::info!("Fetching profile for author {}.", author_id);
+
+    let user = fetch_graphql::<_, UserProfileResult>(
+        state,
+        "user_profile",
+        author_id,
+        GraphQlRequest::from(UserProfileQuery { id: author_id }),
+    )
+    .await?
+    .into_user()?;
+
+    Ok(InsertAuthor {
+        id: user.id,
+        name: user.name,
+        username: user.username,
+        bio: user.bio.unwrap_or_default(),
+        image_id: user.image_id.unwrap_or_default(),
+        follower_count: user.social_stats.map_or(0, |stats| stats.follower_count),
+        details_last_updated_at: chrono::Local::now(),
+    })
+}
+
+async fn update_author(
+    connection: &mut PgConnection,
+    state: &ServerState,
+    author_id: &str,
+) -> anyhow::Result<()> {
+    use crate::db::authors::dsl;
+
+    let author = fetch_author_profile(state, author_id).await?;
+    diesel::insert_into(dsl::authors)
+        .values(&author)
+        .on_conflict(dsl::id)
+        .do_update()
+        .set(&author)
+        .execute(connection)?;
 
     Ok(())
 }
 
-#[allow(non_snake_case, unused)]
-async fn fetch_story_details(postId: &str) -> anyhow::Result<InsertSubmission> {
-    tracing::info!("Fetching details for guid {}.", postId);
-
-    let response = reqwest::Client::new()
-        .post(GRAPHQL_ENDPOINT)
-        .json(&vec![GraphQlRequest::from(PostPageQuery {
-            post_id: postId,
-        })])
-        .send()
-        .await?
-        .json::<PostPageResult>()
-        .await;
-
-    if let Ok(response) = response {
-        if response.len() != 1 {
-            return Err(anyhow!(
-                "Graphql response does not contain a single object."
-            ));
-        }
-        let r = response
-            .into_iter()
-            .next()
-            .ok_or(anyhow!("Unexpected error reading graphql response"))?
-            .data
-            .post_result;
-        return Ok(InsertSubmission {
-            guid: r.id,
-            realname: r.creator.name,
-            username: r.creator.username,
-            latest_published_version: r.latest_published_version,
-            latest_published_at: r.latest_published_at,
-            clap_count: r.clap_count,
-            title: r.title,
-            img_id: r.preview_image.id,
-            word_count: r.word_count,
-        });
-    }
-
-    Err(anyhow!("Error fetching response: {:?}", response))
-}
-
-async fn update_claps(pool: &DbPool) -> anyhow::Result<()> {
+pub(crate) async fn fetch_story_details(state: &ServerState, post_id: &str) -> Result<InsertSubmission, IngestFailure> {
+    tracing::info!("Fetching details for guid {}.", post_id);
+
+    let r = fetch_graphql::<_, PostPageResult>(
+        state,
+        "story_details",
+        post_id,
+        GraphQlRequest::from(PostPageQuery { post_id }),
+    )
+    .await?
+    .into_post()?;
+
+    Ok(InsertSubmission {
+        guid: r.id,
+        realname: r.creator.name,
+        username: r.creator.username,
+        latest_published_version: r.latest_published_version.unwrap_or_default(),
+        latest_published_at: r.latest_published_at.unwrap_or_default(),
+        clap_count: r.clap_count,
+        title: r.title.unwrap_or_default(),
+        img_id: r.preview_image.map(|image| image.id).unwrap_or_default(),
+        word_count: r.word_count.unwrap_or_default(),
+        author_id: Some(r.creator.id),
+        ..Default::default()
+    })
+}
+
+async fn fetch_clap_count(state: &ServerState, post_id: &str) -> Result<i32, IngestFailure> {
+    Ok(fetch_graphql::<_, ClapCountResult>(
+        state,
+        "clap_count",
+        post_id,
+        GraphQlRequest::from(ClapCountQuery {
+            post_id,
+            include_first_boosted_at: false,
+        }),
+    )
+    .await?
+    .into_post()?
+    .clap_count)
+}
+
+async fn fetch_voter_count(state: &ServerState, post_id: &str) -> Result<Option<i32>, IngestFailure> {
+    Ok(fetch_graphql::<_, VoterCountResult>(
+        state,
+        "voter_count",
+        post_id,
+        GraphQlRequest::from(VoterCountQuery { post_id }),
+    )
+    .await?
+    .into_post()?
+    .voter_count)
+}
+
+async fn update_claps(state: &ServerState) -> anyhow::Result<usize> {
     use crate::db::clap_history::dsl;
     use crate::db::submissions::dsl as dsls;
-    let mut connection = pool.get()?;
+    let mut connection = state.pool.get()?;
 
-    match dsls::submissions
-        .select(dsls::clap_count_last_updated_at)
-        .order_by(dsls::clap_count_last_updated_at.desc())
-        .first::<chrono::DateTime<chrono::Local>>(&mut connection)
-    {
-        Ok(date_time) => {
-            if chrono::Local::now().signed_duration_since(date_time) < TimeDelta::minutes(14) {
-                tracing::info!("Checked within last 15 minutes, not checking again!");
-                return Ok(());
-            }
-        }
-        Err(err) => tracing::error!("Error fetching last update time: {}", err),
-    }
     tracing::info!("Updating all clap counts");
 
+    let contest = current_contest(&mut connection, &state.settings)?;
+    let leaders_before = notifications::category_leaders(&mut connection, &state.settings, &contest)?;
+    let fetch_voters = scoring::needs_voter_count(&contest);
+
     let submissions = dsls::submissions
         .select(Submission::as_select())
         .load(&mut connection)
         .expect("Error loading submissions.");
 
-    let client = reqwest::Client::new();
+    let checked = submissions.len();
 
     for submission in submissions {
-        let clap_count = client
-            .post(GRAPHQL_ENDPOINT)
-            .json(&vec![GraphQlRequest::from(ClapCountQuery {
-                post_id: &submission.guid,
-                include_first_boosted_at: false,
-            })])
-            .send()
-            .await?
-            .json::<ClapCountResult>()
-            .await?
-            .into_iter()
-            .next()
-            .ok_or(anyhow!(
-                "Unexpected error reading clap_count graphql response"
-            ))?
-            .data
-            .post_result
-            .clap_count;
+        let clap_count = match fetch_clap_count(state, &submission.guid).await {
+            Ok(clap_count) => clap_count,
+            Err(err) => {
+                record_ingest_error(
+                    &mut connection,
+                    "update_claps",
+                    Some(&submission.guid),
+                    submission.title.clone(),
+                    &err,
+                );
+                if let IngestFailure::RateLimited { .. } = err {
+                    return Err(err.into());
+                }
+                continue;
+            }
+        };
 
         tracing::info!("{}: {}", submission.guid, clap_count);
 
@@ -185,11 +579,48 @@ async fn update_claps(pool: &DbPool) -> anyhow::Result<()> {
                 .execute(&mut connection)
             {
                 tracing::info!("Updated entry in submissions.");
+                record_clap_update();
+
+                if submission.contest_id == contest.id {
+                    let updated = Submission { clap_count, ..submission.clone() };
+                    for milestone in notifications::crossed_milestones(&state.settings, submission.clap_count, clap_count) {
+                        notifications::notify(
+                            &mut connection,
+                            &state.settings,
+                            Notification::clap_milestone(&contest, &updated, milestone),
+                        );
+                    }
+                }
             } else {
                 tracing::error!("Update in submissions failed or affected multiple rows!");
             }
         }
 
+        if fetch_voters && submission.contest_id == contest.id {
+            match fetch_voter_count(state, &submission.guid).await {
+                Ok(voter_count) => {
+                    if let Err(err) = diesel::update(&submission)
+                        .set(dsls::voter_count.eq(voter_count))
+                        .execute(&mut connection)
+                    {
+                        tracing::error!("Updating voter_count failed: {}", err);
+                    }
+                }
+                Err(err) => {
+                    record_ingest_error(
+                        &mut connection,
+                        "update_claps",
+                        Some(&submission.guid),
+                        submission.title.clone(),
+                        &err,
+                    );
+                    if let IngestFailure::RateLimited { .. } = err {
+                        return Err(err.into());
+                    }
+                }
+            }
+        }
+
         if let Ok(1) = diesel::update(&submission)
             .set(dsls::clap_count_last_updated_at.eq(chrono::Local::now()))
             .execute(&mut connection)
@@ -200,28 +631,114 @@ async fn update_claps(pool: &DbPool) -> anyhow::Result<()> {
         }
     }
 
-    Ok(())
+    announce_standings(&mut connection, &state.settings, &contest, &leaders_before)?;
+
+    Ok(checked)
 }
 
-pub fn setup_scheduled_tasks(pool: DbPool) {
-    let mut rss_timer = tokio::time::interval(Duration::from_secs(60 * 60));
-    let mut details_timer = tokio::time::interval(Duration::from_secs(60 * 60 * 24));
-    let mut claps_timer = tokio::time::interval(Duration::from_secs(60 * 15));
-
-    tokio::spawn(async move {
-        loop {
-            tokio::select! {
-                _ = rss_timer.tick() => {
-                    if let Err(e) = update_rss(&pool).await{
-                        tracing::error!("Error fetching rss feed:\n{}" ,e.to_string());
-                    }}
-                _ = details_timer.tick() => {if let Err(e) = update_story_details(&pool).await{
-                        tracing::error!("Error fetching submission details:\n{}" ,e.to_string());
-                    }}
-                _ = claps_timer.tick() => {if let Err(e) = update_claps(&pool).await{
-                        tracing::error!("Error fetching number of claps:\n{}" ,e.to_string());
-                    }}
-            }
+/// Announces categories whose leader changed during the clap update, and the final standings
+/// once the contest has ended.
+fn announce_standings(
+    connection: &mut PgConnection,
+    settings: &Settings,
+    contest: &Contest,
+    leaders_before: &[(crate::models::Category, Submission)],
+) -> anyhow::Result<()> {
+    let leaders = notifications::category_leaders(connection, settings, contest)?;
+
+    for (category, leader) in &leaders {
+        let previous = leaders_before
+            .iter()
+            .find(|(previous_category, _)| previous_category == category)
+            .map(|(_, previous)| previous);
+        if let Some(previous) = previous.filter(|previous| previous.guid != leader.guid) {
+            notifications::notify(connection, settings, Notification::leader_changed(contest, leader, previous));
         }
-    });
+    }
+
+    if contest.ends_at.is_some_and(|ends_at| ends_at <= chrono::Local::now()) {
+        notifications::notify(connection, settings, Notification::contest_closed(contest, &leaders));
+    }
+
+    Ok(())
+}
+
+fn record_ingest_error(
+    connection: &mut PgConnection,
+    job_name: &str,
+    guid: Option<&str>,
+    detail: String,
+    error: &IngestFailure,
+) {
+    use crate::db::ingest_errors::dsl;
+
+    let inserted = InsertIngestError {
+        job_name: job_name.to_owned(),
+        guid: guid.map(str::to_owned),
+        detail,
+        error: error.to_string(),
+        raw_response: error.raw_response().map(str::to_owned),
+    }
+    .insert_into(dsl::ingest_errors)
+    .execute(connection);
+
+    if let Err(err) = inserted {
+        tracing::error!("Error recording ingest error: {}", err);
+    }
+}
+
+/// All jobs. In replay mode, the ones that reach beyond Medium (webhooks, email) or delete archived
+/// responses are left out.
+pub fn job_registry(settings: &Settings) -> JobRegistry {
+    let registry = JobRegistry::new()
+        .register(Job::new(
+            "update_rss",
+            |state| Box::pin(async move { update_rss(&state).await }),
+        ))
+        .register(Job::new(
+            "update_story_details",
+            |state| Box::pin(async move { update_story_details(&state).await }),
+        ))
+        .register(Job::new(
+            "update_claps",
+            |state| Box::pin(async move { update_claps(&state).await }),
+        ))
+        .register(Job::new(
+            "backfill_archive",
+            |state| Box::pin(async move { backfill::backfill_archive(&state).await }),
+        ))
+        .register(Job::new(
+            self_check::JOB_NAME,
+            |state| Box::pin(async move { self_check::check_graphql_schema(&state).await }),
+        ));
+    if settings.archive.replay {
+        return registry;
+    }
+
+    registry
+        .register(Job::new(
+            "prune_response_archive",
+            |state| Box::pin(async move { archive::prune_response_archive(&state).await }),
+        ))
+        .register(Job::new(
+            "deliver_notifications",
+            |state| Box::pin(async move { notifications::deliver_notifications(&state).await }),
+        ))
+        .register(Job::new(
+            email::JOB_NAME,
+            |state| Box::pin(async move { email::send_email_digests(&state).await }),
+        ))
+}
+
+pub fn setup_scheduled_tasks(state: ServerState) -> JobRegistry {
+    let registry = job_registry(&state.settings);
+    registry.start(state.clone());
+
+    // The schedule skips the check if it ran recently, but a new deploy should always verify
+    // its queries.
+    if let Some(job) = registry.get(self_check::JOB_NAME) {
+        tokio::spawn(async move { job.run(&state).await });
+    }
+
+    registry
 }
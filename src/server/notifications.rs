@@ -0,0 +1,341 @@
+use crate::models::{
+    Category, Contest, DeliveryStatus, InsertNotificationDelivery, NotificationDelivery, Submission,
+};
+use crate::server::failure::retry_after;
+use crate::server::scoring;
+use crate::server::settings::{Settings, WebhookKind, WebhookTarget};
+use crate::server::ServerState;
+use diesel::{
+    ExpressionMethods, Insertable, OptionalExtension, PgConnection, QueryDsl, RunQueryDsl,
+    SelectableHelper,
+};
+use dioxus::logger::tracing;
+use serde::Deserialize;
+use std::time::Duration;
+
+const MAX_ATTEMPTS: i32 = 6;
+const BATCH_SIZE: i64 = 50;
+/// Discord rejects longer messages.
+const MAX_MESSAGE_LEN: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    NewSubmission,
+    LeaderChanged,
+    ClapMilestone,
+    ContestClosed,
+}
+
+impl NotificationEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            NotificationEvent::NewSubmission => "new_submission",
+            NotificationEvent::LeaderChanged => "leader_changed",
+            NotificationEvent::ClapMilestone => "clap_milestone",
+            NotificationEvent::ContestClosed => "contest_closed",
+        }
+    }
+
+    fn default_template(&self) -> &'static str {
+        match self {
+            NotificationEvent::NewSubmission => "New in {contest}: **{title}** by {author}\n{url}",
+            NotificationEvent::LeaderChanged => {
+                "{author} now leads {category} with **{title}** ({claps} claps), ahead of {previous_author}.\n{url}"
+            }
+            NotificationEvent::ClapMilestone => "**{title}** by {author} just passed {milestone} claps!\n{url}",
+            NotificationEvent::ContestClosed => "{contest} has closed. Final standings:\n{standings}",
+        }
+    }
+}
+
+/// One announcement. `values` fill the `{placeholders}` of the template.
+pub(crate) struct Notification {
+    event: NotificationEvent,
+    /// The same key is only ever announced once per target.
+    dedup_key: String,
+    values: Vec<(&'static str, String)>,
+    /// Path of the standings card shown with the message.
+    card: String,
+}
+
+impl Notification {
+    fn for_submission(event: NotificationEvent, dedup_key: String, contest: &Contest, submission: &Submission) -> Self {
+        Self {
+            event,
+            dedup_key,
+            values: vec![
+                ("contest", contest.title.clone()),
+                ("title", submission.title.clone()),
+                ("author", submission.realname.clone()),
+                ("username", submission.username.clone()),
+                ("category", submission.category.name().to_owned()),
+                ("claps", submission.clap_count.to_string()),
+                ("url", format!("https://medium.com/p/{}", submission.guid)),
+            ],
+            card: contest.card_path(Some(submission.category)),
+        }
+    }
+
+    pub(crate) fn new_submission(contest: &Contest, submission: &Submission) -> Self {
+        Self::for_submission(
+            NotificationEvent::NewSubmission,
+            format!("new_submission:{}", submission.guid),
+            contest,
+            submission,
+        )
+    }
+
+    pub(crate) fn leader_changed(contest: &Contest, leader: &Submission, previous: &Submission) -> Self {
+        let mut notification = Self::for_submission(
+            NotificationEvent::LeaderChanged,
+            format!(
+                "leader_changed:{}:{}:{}:{}",
+                contest.slug, leader.category as i16, leader.guid, leader.clap_count
+            ),
+            contest,
+            leader,
+        );
+        notification.values.push(("previous_author", previous.realname.clone()));
+        notification.values.push(("previous_title", previous.title.clone()));
+        notification
+    }
+
+    pub(crate) fn clap_milestone(contest: &Contest, submission: &Submission, milestone: i32) -> Self {
+        let mut notification = Self::for_submission(
+            NotificationEvent::ClapMilestone,
+            format!("clap_milestone:{}:{}", submission.guid, milestone),
+            contest,
+            submission,
+        );
+        notification.values.push(("milestone", milestone.to_string()));
+        notification
+    }
+
+    pub(crate) fn contest_closed(contest: &Contest, leaders: &[(Category, Submission)]) -> Self {
+        let standings = leaders
+            .iter()
+            .map(|(category, submission)| {
+                format!(
+                    "{}: **{}** by {} ({} claps)",
+                    category.name(),
+                    submission.title,
+                    submission.realname,
+                    submission.clap_count
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Self {
+            event: NotificationEvent::ContestClosed,
+            dedup_key: format!("contest_closed:{}", contest.slug),
+            values: vec![("contest", contest.title.clone()), ("standings", standings)],
+            card: contest.card_path(None),
+        }
+    }
+
+    fn render(&self, template: &str) -> String {
+        let message = self
+            .values
+            .iter()
+            .fold(template.to_owned(), |message, (key, value)| {
+                message.replace(&format!("{{{}}}", key), value)
+            });
+
+        match message.char_indices().nth(MAX_MESSAGE_LEN - 1) {
+            Some((index, _)) => format!("{}…", &message[..index]),
+            None => message,
+        }
+    }
+}
+
+/// Queues the notification for every target subscribed to its event. Failures are logged, a
+/// notification never fails the job that raised it.
+pub(crate) fn notify(connection: &mut PgConnection, settings: &Settings, notification: Notification) {
+    use crate::db::notification_deliveries::dsl;
+    // Chat services cache images by url, the timestamp makes them fetch the current standings.
+    let image_url = format!(
+        "{}{}?at={}",
+        settings.leaderboard.public_url(),
+        notification.card,
+        chrono::Utc::now().timestamp()
+    );
+
+    for target in settings.notifications.targets.iter().filter(|target| target.wants(notification.event)) {
+        let template = target
+            .templates
+            .get(&notification.event)
+            .or(settings.notifications.templates.get(&notification.event))
+            .map(String::as_str)
+            .unwrap_or(notification.event.default_template());
+
+        let queued = InsertNotificationDelivery {
+            target: target.name.clone(),
+            event: notification.event.name().to_owned(),
+            dedup_key: notification.dedup_key.clone(),
+            message: notification.render(template),
+            image_url: Some(image_url.clone()),
+        }
+        .insert_into(dsl::notification_deliveries)
+        .on_conflict((dsl::target, dsl::dedup_key))
+        .do_nothing()
+        .execute(connection);
+
+        match queued {
+            Ok(1) => tracing::info!("Queued {} for {}.", notification.dedup_key, target.name),
+            Ok(_) => {}
+            Err(err) => tracing::error!("Error queueing {}: {}", notification.dedup_key, err),
+        }
+    }
+}
+
+/// Announces a submission that was just ingested.
+pub(crate) fn announce_submission(connection: &mut PgConnection, settings: &Settings, contest: &Contest, guid: &str) {
+    use crate::db::submissions::dsl;
+
+    match dsl::submissions
+        .find(guid)
+        .select(Submission::as_select())
+        .first(connection)
+        .optional()
+    {
+        Ok(Some(submission)) => notify(connection, settings, Notification::new_submission(contest, &submission)),
+        Ok(None) => {}
+        Err(err) => tracing::error!("Error loading submission {} for notification: {}", guid, err),
+    }
+}
+
+/// The first ranked entry in every category of the contest, by its scoring strategy and the
+/// configured tie-break.
+pub(crate) fn category_leaders(
+    connection: &mut PgConnection,
+    settings: &Settings,
+    contest: &Contest,
+) -> anyhow::Result<Vec<(Category, Submission)>> {
+    let ranked = scoring::score_contest(connection, settings, contest)?;
+
+    let mut leaders: Vec<(Category, Submission)> = Vec::new();
+    for entry in ranked {
+        let category = entry.submission.category;
+        if category != Category::None && !leaders.iter().any(|(leader, _)| *leader == category) {
+            leaders.push((category, entry.submission));
+        }
+    }
+    leaders.sort_by_key(|(category, _)| *category as i16);
+
+    Ok(leaders)
+}
+
+/// Milestones passed when the clap count went from `before` to `after`.
+pub(crate) fn crossed_milestones(settings: &Settings, before: i32, after: i32) -> Vec<i32> {
+    settings
+        .notifications
+        .clap_milestones
+        .iter()
+        .copied()
+        .filter(|milestone| before < *milestone && *milestone <= after)
+        .collect()
+}
+
+/// Sends queued notifications that are due. Failed deliveries are retried with exponential
+/// backoff, or after `Retry-After` if the webhook asks for it.
+pub(crate) async fn deliver_notifications(state: &ServerState) -> anyhow::Result<usize> {
+    use crate::db::notification_deliveries::dsl;
+    let mut connection = state.pool.get()?;
+
+    let pending = dsl::notification_deliveries
+        .filter(dsl::status.eq(DeliveryStatus::Pending))
+        .filter(dsl::next_attempt_at.le(chrono::Local::now()))
+        .order_by(dsl::created_at.asc())
+        .limit(BATCH_SIZE)
+        .select(NotificationDelivery::as_select())
+        .load(&mut connection)?;
+    if pending.is_empty() {
+        return Ok(0);
+    }
+
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?;
+    let mut delivered = 0;
+
+    for delivery in pending {
+        let target = state
+            .settings
+            .notifications
+            .targets
+            .iter()
+            .find(|target| target.name == delivery.target);
+        let result = match target {
+            Some(target) => send(&client, target, &delivery.message, delivery.image_url.as_deref()).await,
+            None => Err((format!("Unknown target {}", delivery.target), None)),
+        };
+
+        let attempts = delivery.attempts + 1;
+        match result {
+            Ok(()) => {
+                diesel::update(&delivery)
+                    .set((
+                        dsl::status.eq(DeliveryStatus::Delivered),
+                        dsl::attempts.eq(attempts),
+                        dsl::last_error.eq(None::<String>),
+                        dsl::delivered_at.eq(chrono::Local::now()),
+                    ))
+                    .execute(&mut connection)?;
+                delivered += 1;
+            }
+            Err((error, retry_after)) => {
+                tracing::warn!("Delivering notification {} failed: {}", delivery.id, error);
+                let gave_up = attempts >= MAX_ATTEMPTS || target.is_none();
+                let delay = retry_after.unwrap_or(Duration::from_secs(60) * 2u32.pow(attempts as u32 - 1));
+
+                diesel::update(&delivery)
+                    .set((
+                        dsl::status.eq(if gave_up { DeliveryStatus::Failed } else { DeliveryStatus::Pending }),
+                        dsl::attempts.eq(attempts),
+                        dsl::last_error.eq(error),
+                        dsl::next_attempt_at.eq(chrono::Local::now() + chrono::TimeDelta::from_std(delay)?),
+                    ))
+                    .execute(&mut connection)?;
+            }
+        }
+    }
+
+    Ok(delivered)
+}
+
+async fn send(
+    client: &reqwest::Client,
+    target: &WebhookTarget,
+    message: &str,
+    image_url: Option<&str>,
+) -> Result<(), (String, Option<Duration>)> {
+    let url = target.url().map_err(|err| (err.to_string(), None))?;
+    let payload = match (target.kind, image_url) {
+        (WebhookKind::Discord, None) => serde_json::json!({ "content": message }),
+        (WebhookKind::Discord, Some(image_url)) => serde_json::json!({
+            "content": message,
+            "embeds": [{ "image": { "url": image_url } }],
+        }),
+        (WebhookKind::Slack, None) => serde_json::json!({ "text": message }),
+        (WebhookKind::Slack, Some(image_url)) => serde_json::json!({
+            "text": message,
+            "blocks": [
+                { "type": "section", "text": { "type": "mrkdwn", "text": message } },
+                { "type": "image", "image_url": image_url, "alt_text": "Current standings" },
+            ],
+        }),
+    };
+
+    let response = client
+        .post(url)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|err| (err.to_string(), None))?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err((format!("Webhook answered {}", response.status()), retry_after(&response)))
+    }
+}
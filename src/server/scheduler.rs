@@ -0,0 +1,288 @@
+use crate::db::DbPool;
+use crate::models::{InsertJobRun, JobOverview, JobRun, JobSchedule, JobStatus};
+use crate::server::lease::Lease;
+use crate::server::telemetry::record_job_run;
+use crate::server::ServerState;
+use diesel::{
+    ExpressionMethods, Insertable, OptionalExtension, QueryDsl, RunQueryDsl, SelectableHelper,
+};
+use dioxus::logger::tracing;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+pub type JobFuture = Pin<Box<dyn Future<Output = anyhow::Result<usize>> + Send>>;
+
+/// How often a job re-reads its interval, so schedule changes apply without a restart.
+const RESCHEDULE_CHECK: Duration = Duration::from_secs(60);
+
+/// A named unit of scheduled work. The job function returns the number of items it processed.
+pub struct Job {
+    pub name: &'static str,
+    run: fn(ServerState) -> JobFuture,
+    running: tokio::sync::Mutex<()>,
+}
+
+impl Job {
+    pub fn new(name: &'static str, run: fn(ServerState) -> JobFuture) -> Self {
+        Self {
+            name,
+            run,
+            running: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Interval from the `[jobs]` settings, then `job_schedules`, then the default in the
+    /// settings. `None` if the job is disabled.
+    pub fn interval(&self, state: &ServerState) -> Option<Duration> {
+        use crate::db::job_schedules::dsl;
+
+        let configured = state.settings.job(self.name);
+        if configured.enabled == Some(false) {
+            return None;
+        }
+        if let Some(interval) = configured.interval_seconds {
+            return Some(Duration::from_secs(interval));
+        }
+
+        let schedule = state.pool.get().map_err(anyhow::Error::from).and_then(|mut connection| {
+            Ok(dsl::job_schedules
+                .find(self.name)
+                .select(JobSchedule::as_select())
+                .first(&mut connection)
+                .optional()?)
+        });
+
+        match schedule {
+            Ok(Some(schedule)) if !schedule.enabled => None,
+            Ok(Some(schedule)) => Some(Duration::from_secs(schedule.interval_seconds.max(1) as u64)),
+            Ok(None) => Some(state.settings.default_job_interval(self.name)),
+            Err(err) => {
+                tracing::error!("Error loading schedule for {}: {}", self.name, err);
+                Some(state.settings.default_job_interval(self.name))
+            }
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.try_lock().is_err()
+    }
+
+    /// Whether the last successful run, on any instance, is at least one period ago.
+    fn is_due(&self, pool: &DbPool, period: Duration) -> anyhow::Result<bool> {
+        use crate::db::job_runs::dsl;
+        let mut connection = pool.get()?;
+
+        // Timers of different instances are not aligned, allow them to drift by a tenth.
+        let threshold = chrono::Local::now() - chrono::TimeDelta::from_std(period - period / 10)?;
+        let recent_runs = dsl::job_runs
+            .filter(dsl::job_name.eq(self.name))
+            .filter(dsl::status.eq(JobStatus::Succeeded))
+            .filter(dsl::started_at.gt(threshold))
+            .count()
+            .get_result::<i64>(&mut connection)?;
+
+        Ok(recent_runs == 0)
+    }
+
+    /// Runs the job and records it in `job_runs`. Returns `None` without running if the previous
+    /// run has not finished yet, on this or another instance.
+    pub async fn run(&self, state: &ServerState) -> Option<JobStatus> {
+        let pool = &state.pool;
+        let Ok(_running) = self.running.try_lock() else {
+            tracing::warn!("Job {} is still running, skipping this run.", self.name);
+            return None;
+        };
+
+        let lease = match Lease::try_acquire(pool, self.name) {
+            Ok(Some(lease)) => lease,
+            Ok(None) => {
+                tracing::info!("Job {} is running on another instance, skipping this run.", self.name);
+                return None;
+            }
+            Err(err) => {
+                tracing::error!("Error acquiring lease for job {}: {}", self.name, err);
+                return None;
+            }
+        };
+
+        tracing::info!("Starting job {}.", self.name);
+        let run_id = match start_run(pool, self.name) {
+            Ok(run_id) => Some(run_id),
+            Err(err) => {
+                tracing::error!("Error recording start of job {}: {}", self.name, err);
+                None
+            }
+        };
+
+        let started = Instant::now();
+        // Dropping the job at its next await point stops it from polling Medium alongside the
+        // instance that took over.
+        let result = tokio::select! {
+            result = (self.run)(state.clone()) => result,
+            _ = lease.lost() => Err(anyhow::anyhow!("Lost the lease, aborted the run.")),
+        };
+        let status = match &result {
+            Ok(items) => {
+                tracing::info!("Job {} processed {} items.", self.name, items);
+                JobStatus::Succeeded
+            }
+            Err(err) => {
+                tracing::error!("Error running job {}:\n{}", self.name, err);
+                JobStatus::Failed
+            }
+        };
+
+        record_job_run(
+            self.name,
+            if status == JobStatus::Succeeded { "succeeded" } else { "failed" },
+            started.elapsed(),
+        );
+
+        if let Some(run_id) = run_id {
+            if let Err(err) = finish_run(pool, run_id, status, &result) {
+                tracing::error!("Error recording end of job {}: {}", self.name, err);
+            }
+        }
+        lease.release();
+
+        Some(status)
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct JobRegistry {
+    jobs: Vec<Arc<Job>>,
+}
+
+impl JobRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(mut self, job: Job) -> Self {
+        self.jobs.push(Arc::new(job));
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<Job>> {
+        self.jobs.iter().find(|job| job.name == name).cloned()
+    }
+
+    pub fn jobs(&self) -> impl Iterator<Item = &Arc<Job>> {
+        self.jobs.iter()
+    }
+
+    pub fn overview(&self, state: &ServerState) -> anyhow::Result<Vec<JobOverview>> {
+        use crate::db::job_runs::dsl;
+        let mut connection = state.pool.get()?;
+
+        self.jobs
+            .iter()
+            .map(|job| {
+                let last_run = dsl::job_runs
+                    .filter(dsl::job_name.eq(job.name))
+                    .order_by(dsl::started_at.desc())
+                    .select(JobRun::as_select())
+                    .first(&mut connection)
+                    .optional()?;
+                let interval = job.interval(state);
+                let next_run = match (&last_run, interval) {
+                    (Some(last_run), Some(interval)) => {
+                        Some(last_run.started_at + chrono::TimeDelta::from_std(interval)?)
+                    }
+                    (None, Some(_)) => Some(chrono::Local::now()),
+                    (_, None) => None,
+                };
+
+                Ok(JobOverview {
+                    name: job.name.to_owned(),
+                    interval_seconds: interval.map(|interval| interval.as_secs() as i64),
+                    running: job.is_running(),
+                    last_run,
+                    next_run,
+                })
+            })
+            .collect()
+    }
+
+    /// Spawns one task per job, so a slow job does not delay the others. The interval is read
+    /// again on every pass, a changed or re-enabled schedule applies within a minute.
+    pub fn start(&self, state: ServerState) {
+        for job in self.jobs.iter().cloned() {
+            let state = state.clone();
+            tokio::spawn(async move {
+                let mut scheduled = None;
+                let mut last_attempt: Option<Instant> = None;
+                loop {
+                    let interval = job.interval(&state);
+                    if interval != scheduled {
+                        match interval {
+                            Some(period) => tracing::info!("Scheduling job {} every {:?}.", job.name, period),
+                            None => tracing::info!("Job {} is disabled.", job.name),
+                        }
+                        scheduled = interval;
+                    }
+
+                    let Some(period) = interval else {
+                        tokio::time::sleep(RESCHEDULE_CHECK).await;
+                        continue;
+                    };
+                    if last_attempt.is_none_or(|at| at.elapsed() >= period) {
+                        last_attempt = Some(Instant::now());
+                        match job.is_due(&state.pool, period) {
+                            Ok(true) => {
+                                job.run(&state).await;
+                            }
+                            Ok(false) => tracing::info!("Job {} ran recently, not running again.", job.name),
+                            Err(err) => tracing::error!("Error checking last run of {}: {}", job.name, err),
+                        }
+                    }
+
+                    let next = last_attempt.map_or(Duration::ZERO, |at| period.saturating_sub(at.elapsed()));
+                    tokio::time::sleep(next.min(RESCHEDULE_CHECK)).await;
+                }
+            });
+        }
+    }
+}
+
+fn start_run(pool: &DbPool, job_name: &str) -> anyhow::Result<i32> {
+    use crate::db::job_runs::dsl;
+    let mut connection = pool.get()?;
+
+    Ok(InsertJobRun {
+        job_name: job_name.to_owned(),
+        status: JobStatus::Running,
+    }
+    .insert_into(dsl::job_runs)
+    .returning(dsl::id)
+    .get_result(&mut connection)?)
+}
+
+fn finish_run(
+    pool: &DbPool,
+    run_id: i32,
+    status: JobStatus,
+    result: &anyhow::Result<usize>,
+) -> anyhow::Result<()> {
+    use crate::db::job_runs::dsl;
+    let mut connection = pool.get()?;
+
+    let (items_processed, error) = match result {
+        Ok(items) => (*items as i32, None),
+        Err(err) => (0, Some(format!("{:#}", err))),
+    };
+
+    diesel::update(dsl::job_runs.find(run_id))
+        .set((
+            dsl::finished_at.eq(chrono::Local::now()),
+            dsl::status.eq(status),
+            dsl::items_processed.eq(items_processed),
+            dsl::error.eq(error),
+        ))
+        .execute(&mut connection)?;
+
+    Ok(())
+}
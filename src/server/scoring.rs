@@ -0,0 +1,487 @@
+use crate::models::{Category, Contest, ScoredSubmission, Scoring, Submission, TieBreak};
+use crate::server::jury;
+use crate::server::settings::Settings;
+use chrono::{DateTime, Local, TimeZone};
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use std::collections::HashMap;
+
+/// What a strategy may use besides the submission itself.
+pub(crate) struct ScoreContext {
+    /// The moment scored, never after the end of the contest.
+    pub(crate) as_of: DateTime<Local>,
+    pub(crate) max_claps: HashMap<i16, i32>,
+    /// Jury results on a 0–100 scale, by guid.
+    pub(crate) judge_scores: HashMap<String, f64>,
+}
+
+pub(crate) struct Score {
+    pub(crate) value: f64,
+    pub(crate) breakdown: String,
+}
+
+pub(crate) trait ScoringStrategy {
+    fn score(&self, submission: &Submission, context: &ScoreContext) -> Score;
+}
+
+struct ClapCount;
+
+impl ScoringStrategy for ClapCount {
+    fn score(&self, submission: &Submission, _: &ScoreContext) -> Score {
+        Score {
+            value: submission.clap_count as f64,
+            breakdown: format!("{} claps", submission.clap_count),
+        }
+    }
+}
+
+struct ClapsPerDay;
+
+impl ScoringStrategy for ClapsPerDay {
+    fn score(&self, submission: &Submission, context: &ScoreContext) -> Score {
+        let published_at = Local
+            .timestamp_millis_opt(submission.latest_published_at)
+            .single()
+            .unwrap_or(context.as_of);
+        // Less than a day counts as one, or fresh posts would get huge scores.
+        let days = ((context.as_of - published_at).num_hours() as f64 / 24.0).max(1.0);
+
+        Score {
+            value: submission.clap_count as f64 / days,
+            breakdown: format!("{} claps / {:.1} days", submission.clap_count, days),
+        }
+    }
+}
+
+struct ClapsPerThousandWords;
+
+impl ScoringStrategy for ClapsPerThousandWords {
+    fn score(&self, submission: &Submission, _: &ScoreContext) -> Score {
+        // Very short pieces, and posts whose word count is unknown, count as 100 words.
+        let words = submission.word_count.max(100);
+
+        Score {
+            value: submission.clap_count as f64 * 1000.0 / words as f64,
+            breakdown: format!("{} claps / {} words × 1000", submission.clap_count, words),
+        }
+    }
+}
+
+struct UniqueClappers;
+
+impl ScoringStrategy for UniqueClappers {
+    fn score(&self, submission: &Submission, _: &ScoreContext) -> Score {
+        match submission.voter_count {
+            Some(voters) => Score {
+                value: voters as f64,
+                breakdown: format!("{} readers clapped", voters),
+            },
+            None => Score {
+                value: 0.0,
+                breakdown: "Unique clappers not fetched yet".to_owned(),
+            },
+        }
+    }
+}
+
+struct Blend {
+    clap_weight: f64,
+    judge_weight: f64,
+}
+
+impl ScoringStrategy for Blend {
+    fn score(&self, submission: &Submission, context: &ScoreContext) -> Score {
+        let max_claps = context
+            .max_claps
+            .get(&(submission.category as i16))
+            .copied()
+            .unwrap_or_default()
+            .max(1);
+        let clap_share = submission.clap_count as f64 * 100.0 / max_claps as f64;
+        let judge_score = context.judge_scores.get(&submission.guid).copied();
+        let total_weight = self.clap_weight + self.judge_weight;
+
+        let value = (self.clap_weight * clap_share + self.judge_weight * judge_score.unwrap_or_default()) / total_weight;
+        let breakdown = match judge_score {
+            Some(judge_score) => format!(
+                "{:.0}% × {:.1} claps ({} of {}) + {:.0}% × {:.1} jury",
+                self.clap_weight * 100.0 / total_weight,
+                clap_share,
+                submission.clap_count,
+                max_claps,
+                self.judge_weight * 100.0 / total_weight,
+                judge_score
+            ),
+            None => format!(
+                "{:.0}% × {:.1} claps ({} of {}), no published jury score",
+                self.clap_weight * 100.0 / total_weight,
+                clap_share,
+                submission.clap_count,
+                max_claps
+            ),
+        };
+
+        Score { value, breakdown }
+    }
+}
+
+pub(crate) fn strategy(scoring: Scoring) -> Box<dyn ScoringStrategy> {
+    match scoring {
+        Scoring::Claps => Box::new(ClapCount),
+        Scoring::ClapsPerDay => Box::new(ClapsPerDay),
+        Scoring::ClapsPerThousandWords => Box::new(ClapsPerThousandWords),
+        Scoring::UniqueClappers => Box::new(UniqueClappers),
+        Scoring::Blend {
+            clap_weight,
+            judge_weight,
+        } => Box::new(Blend {
+            clap_weight,
+            judge_weight,
+        }),
+    }
+}
+
+/// Weights of a blend must not be negative and must not add up to zero.
+pub fn validate(scoring: &Scoring) -> anyhow::Result<()> {
+    if let Scoring::Blend {
+        clap_weight,
+        judge_weight,
+    } = scoring
+    {
+        if *clap_weight < 0.0
+            || *judge_weight < 0.0
+            || (clap_weight + judge_weight).is_nan()
+            || clap_weight + judge_weight <= 0.0
+        {
+            anyhow::bail!("Blend weights must not be negative and must not both be zero");
+        }
+    }
+    Ok(())
+}
+
+/// Scores `entries` with the strategy of `contest`, best first. Ties are left in title order
+/// for [`rank_with`].
+pub(crate) fn score_entries(
+    contest: &Contest,
+    entries: Vec<Submission>,
+    judge_scores: HashMap<String, f64>,
+    as_of: DateTime<Local>,
+) -> Vec<ScoredSubmission> {
+    let mut max_claps = HashMap::new();
+    for entry in &entries {
+        let max = max_claps.entry(entry.category as i16).or_insert(0);
+        *max = (*max).max(entry.clap_count);
+    }
+    let context = ScoreContext {
+        as_of,
+        max_claps,
+        judge_scores,
+    };
+
+    let strategy = strategy(contest.scoring());
+    let mut scored: Vec<ScoredSubmission> = entries
+        .into_iter()
+        .map(|submission| {
+            let score = strategy.score(&submission, &context);
+            ScoredSubmission {
+                submission,
+                score: score.value,
+                breakdown: score.breakdown,
+                ..Default::default()
+            }
+        })
+        .collect();
+    scored.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(a.submission.title.cmp(&b.submission.title))
+    });
+
+    scored
+}
+
+/// When the entry first had its current clap count. Entries whose count never changed since they
+/// were ingested have no history, they count as having reached it when they were published.
+fn reached_count_at(connection: &mut PgConnection, submission: &Submission) -> anyhow::Result<DateTime<Local>> {
+    use crate::db::clap_history::dsl;
+
+    let reached_at = dsl::clap_history
+        .filter(dsl::guid.eq(&submission.guid))
+        .filter(dsl::clap_count.ge(submission.clap_count))
+        .select(diesel::dsl::min(dsl::timestamp))
+        .first::<Option<DateTime<Local>>>(connection)?;
+
+    Ok(reached_at.unwrap_or_else(|| {
+        Local
+            .timestamp_millis_opt(submission.latest_published_at)
+            .single()
+            .unwrap_or_default()
+    }))
+}
+
+/// Ranks the entries in `tied`, which share a score, starting at `first_rank`. Entries the
+/// tie-break can not tell apart still share their rank.
+fn break_tie(
+    tie_break: TieBreak,
+    scored: &mut [ScoredSubmission],
+    tied: &[usize],
+    first_rank: usize,
+    reached_at: &mut impl FnMut(&Submission) -> anyhow::Result<DateTime<Local>>,
+) -> anyhow::Result<()> {
+    let mut keys = Vec::with_capacity(tied.len());
+    for &index in tied {
+        let submission = &scored[index].submission;
+        keys.push(match tie_break {
+            TieBreak::Shared => 0,
+            TieBreak::Earliest => reached_at(submission)?.timestamp_millis(),
+            TieBreak::FewerWords => submission.word_count as i64,
+        });
+    }
+
+    for (position, &index) in tied.iter().enumerate() {
+        let key = keys[position];
+        let ahead = keys.iter().filter(|other| **other < key).count();
+        let sharing = keys.iter().filter(|other| **other == key).count() - 1;
+        let entry = &mut scored[index];
+        entry.rank = first_rank + ahead;
+        let others = if sharing == 1 { "entry" } else { "entries" };
+        entry.tie_break = Some(match tie_break {
+            TieBreak::Shared => format!("Tied with {} other {}", sharing, others),
+            _ if sharing > 0 => format!("Tied, the tie-break does not separate it from {} other {}", sharing, others),
+            TieBreak::Earliest => format!(
+                "Tied, reached {} claps {} of {}",
+                entry.submission.clap_count,
+                ordinal(ahead + 1),
+                tied.len()
+            ),
+            TieBreak::FewerWords => format!(
+                "Tied, {} shortest of {} with {} words",
+                ordinal(ahead + 1),
+                tied.len(),
+                entry.submission.word_count
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+/// Assigns the rank within each category to `scored`, which is ordered by score, and orders it
+/// by rank. `reached_at` tells when an entry reached its clap count.
+fn rank_with(
+    tie_break: TieBreak,
+    mut scored: Vec<ScoredSubmission>,
+    mut reached_at: impl FnMut(&Submission) -> anyhow::Result<DateTime<Local>>,
+) -> anyhow::Result<Vec<ScoredSubmission>> {
+    for category in Category::ALL {
+        let indices: Vec<usize> = scored
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.submission.category == category)
+            .map(|(index, _)| index)
+            .collect();
+
+        let mut position = 0;
+        while position < indices.len() {
+            let score = scored[indices[position]].score;
+            let tied: Vec<usize> = indices[position..]
+                .iter()
+                .copied()
+                .take_while(|index| scored[*index].score == score)
+                .collect();
+
+            if let [index] = tied[..] {
+                scored[index].rank = position + 1;
+            } else {
+                break_tie(tie_break, &mut scored, &tied, position + 1, &mut reached_at)?;
+            }
+            position += tied.len();
+        }
+    }
+    scored.sort_by_key(|entry| entry.rank);
+
+    Ok(scored)
+}
+
+/// The ranked entries of a contest, excluding the organizers' posts.
+pub(crate) fn score_contest(
+    connection: &mut PgConnection,
+    settings: &Settings,
+    contest: &Contest,
+) -> anyhow::Result<Vec<ScoredSubmission>> {
+    use crate::db::submissions::dsl;
+
+    let entries = dsl::submissions
+        .filter(dsl::contest_id.eq(contest.id))
+        .filter(dsl::username.ne_all(&settings.leaderboard.excluded_usernames))
+        .select(Submission::as_select())
+        .load(connection)?;
+
+    rank_as_of(connection, settings, contest, entries, Local::now())
+}
+
+/// Scores and ranks `entries` as they stood at `as_of`. Scores are frozen once the contest has
+/// ended.
+pub(crate) fn rank_as_of(
+    connection: &mut PgConnection,
+    settings: &Settings,
+    contest: &Contest,
+    entries: Vec<Submission>,
+    as_of: DateTime<Local>,
+) -> anyhow::Result<Vec<ScoredSubmission>> {
+    let judge_scores = judge_scores(connection, settings, contest)?;
+    rank_with_judges(settings, contest, entries, judge_scores, as_of, |submission| {
+        reached_count_at(connection, submission)
+    })
+}
+
+/// The jury results counted in the ranking, by guid. The jury only counts once its results are
+/// published.
+pub(crate) fn judge_scores(
+    connection: &mut PgConnection,
+    settings: &Settings,
+    contest: &Contest,
+) -> anyhow::Result<HashMap<String, f64>> {
+    if contest.jury_published_at.is_none() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(jury::results(connection, settings, contest.id)?
+        .into_values()
+        .map(|result| (result.guid, result.score))
+        .collect())
+}
+
+/// [`rank_as_of`] with the [`judge_scores`] already loaded, and `reached_at` telling when an entry
+/// reached its clap count for the earliest tie-break.
+pub(crate) fn rank_with_judges(
+    settings: &Settings,
+    contest: &Contest,
+    entries: Vec<Submission>,
+    judge_scores: HashMap<String, f64>,
+    as_of: DateTime<Local>,
+    reached_at: impl FnMut(&Submission) -> anyhow::Result<DateTime<Local>>,
+) -> anyhow::Result<Vec<ScoredSubmission>> {
+    let as_of = contest.ends_at.map_or(as_of, |ends_at| as_of.min(ends_at));
+
+    rank_with(
+        settings.leaderboard.tie_break,
+        score_entries(contest, entries, judge_scores, as_of),
+        reached_at,
+    )
+}
+
+/// Whether `update_claps` should also fetch the unique clappers.
+pub(crate) fn needs_voter_count(contest: &Contest) -> bool {
+    contest.scoring() == Scoring::UniqueClappers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(title: &str, category: Category, score: f64, word_count: i32) -> ScoredSubmission {
+        ScoredSubmission {
+            submission: Submission {
+                guid: title.to_owned(),
+                title: title.to_owned(),
+                category,
+                word_count,
+                ..Default::default()
+            },
+            score,
+            ..Default::default()
+        }
+    }
+
+    fn ranks(ranked: &[ScoredSubmission]) -> Vec<(&str, usize)> {
+        ranked
+            .iter()
+            .map(|entry| (entry.submission.title.as_str(), entry.rank))
+            .collect()
+    }
+
+    fn no_history(_: &Submission) -> anyhow::Result<DateTime<Local>> {
+        anyhow::bail!("Not needed by this tie-break")
+    }
+
+    #[test]
+    fn shared_ties_skip_the_next_rank() {
+        let scored = vec![
+            entry("a", Category::Poetry, 10.0, 0),
+            entry("b", Category::Poetry, 10.0, 0),
+            entry("c", Category::Poetry, 5.0, 0),
+        ];
+
+        let ranked = rank_with(TieBreak::Shared, scored, no_history).unwrap();
+
+        assert_eq!(ranks(&ranked), vec![("a", 1), ("b", 1), ("c", 3)]);
+        assert_eq!(ranked[0].tie_break.as_deref(), Some("Tied with 1 other entry"));
+        assert_eq!(ranked[2].tie_break, None);
+    }
+
+    #[test]
+    fn categories_are_ranked_separately() {
+        let scored = vec![
+            entry("a", Category::Poetry, 10.0, 0),
+            entry("b", Category::Fiction, 8.0, 0),
+            entry("c", Category::Poetry, 5.0, 0),
+        ];
+
+        let ranked = rank_with(TieBreak::Shared, scored, no_history).unwrap();
+
+        assert_eq!(ranks(&ranked), vec![("a", 1), ("b", 1), ("c", 2)]);
+    }
+
+    #[test]
+    fn fewer_words_ranks_the_shorter_entry_higher() {
+        let scored = vec![
+            entry("a", Category::PersonalEssay, 7.0, 1200),
+            entry("b", Category::PersonalEssay, 7.0, 800),
+            entry("c", Category::PersonalEssay, 7.0, 1200),
+            entry("d", Category::PersonalEssay, 3.0, 100),
+        ];
+
+        let ranked = rank_with(TieBreak::FewerWords, scored, no_history).unwrap();
+
+        assert_eq!(ranks(&ranked), vec![("b", 1), ("a", 2), ("c", 2), ("d", 4)]);
+        assert_eq!(ranked[0].tie_break.as_deref(), Some("Tied, 1st shortest of 3 with 800 words"));
+        assert_eq!(
+            ranked[1].tie_break.as_deref(),
+            Some("Tied, the tie-break does not separate it from 1 other entry")
+        );
+    }
+
+    #[test]
+    fn earliest_ranks_the_entry_that_reached_its_count_first() {
+        let scored = vec![
+            entry("a", Category::Fiction, 7.0, 0),
+            entry("b", Category::Fiction, 7.0, 0),
+        ];
+        let reached_at = |submission: &Submission| {
+            let hour = if submission.title == "a" { 12 } else { 9 };
+            Ok(Local.with_ymd_and_hms(2025, 1, 10, hour, 0, 0).unwrap())
+        };
+
+        let ranked = rank_with(TieBreak::Earliest, scored, reached_at).unwrap();
+
+        assert_eq!(ranks(&ranked), vec![("b", 1), ("a", 2)]);
+    }
+
+    #[test]
+    fn ordinals() {
+        let ordinals: Vec<String> = [1, 2, 3, 4, 11, 12, 13, 21, 112].into_iter().map(ordinal).collect();
+
+        assert_eq!(ordinals, ["1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "112th"]);
+    }
+}
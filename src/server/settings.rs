@@ -0,0 +1,690 @@
+use crate::models::{JuryCriterion, Scoring, TieBreak};
+use crate::server::notifications::NotificationEvent;
+use anyhow::{anyhow, bail};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+const SETTINGS_FILE: &str = "settings.toml";
+/// `LEADERBOARD__MEDIUM__FEED_URL=...` overrides `feed_url` in the `[medium]` table.
+const ENV_PREFIX: &str = "LEADERBOARD__";
+/// Job intervals in seconds when neither `[jobs]` nor `job_schedules` sets one.
+const DEFAULT_JOB_INTERVALS: &[(&str, u64)] = &[
+    ("update_rss", 60 * 60),
+    ("update_story_details", 60 * 60 * 24),
+    ("update_claps", 60 * 15),
+    ("backfill_archive", 60 * 60 * 6),
+    ("check_graphql_schema", 60 * 60 * 24),
+    ("prune_response_archive", 60 * 60 * 24),
+    ("deliver_notifications", 60),
+    ("send_email_digests", 60 * 60 * 24),
+];
+/// Interval of jobs missing from [`DEFAULT_JOB_INTERVALS`].
+const FALLBACK_JOB_INTERVAL: u64 = 60 * 60;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub medium: MediumSettings,
+    pub contest: ContestSettings,
+    pub leaderboard: LeaderboardSettings,
+    pub archive: ArchiveSettings,
+    pub http: HttpSettings,
+    pub notifications: NotificationSettings,
+    pub email: EmailSettings,
+    pub jury: JurySettings,
+    pub voting: VotingSettings,
+    pub jobs: HashMap<String, JobSettings>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MediumSettings {
+    pub feed_url: String,
+    pub graphql_endpoint: String,
+    pub tag_slug: String,
+    pub publication_slug: String,
+}
+
+impl Default for MediumSettings {
+    fn default() -> Self {
+        Self {
+            feed_url: "https://medium.com/feed/my-fair-lighthouse/tagged/mfl-contest".to_owned(),
+            graphql_endpoint: "https://medium.com/_/graphql".to_owned(),
+            tag_slug: "mfl-contest".to_owned(),
+            publication_slug: "my-fair-lighthouse".to_owned(),
+        }
+    }
+}
+
+/// The contest new submissions are ingested into. Created on startup if missing.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ContestSettings {
+    pub slug: String,
+    pub title: String,
+    pub publication: String,
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub ends_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Overrides the strategy selected on the admin page.
+    pub scoring: Option<Scoring>,
+}
+
+impl Default for ContestSettings {
+    fn default() -> Self {
+        Self {
+            slug: "transformation".to_owned(),
+            title: "Transformation".to_owned(),
+            publication: "My Fair Lighthouse".to_owned(),
+            starts_at: None,
+            ends_at: None,
+            scoring: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LeaderboardSettings {
+    /// Accounts of the organizers, their posts are not ranked.
+    pub excluded_usernames: Vec<String>,
+    pub display_version: String,
+    /// Address this site is reachable at, for links in feeds and emails.
+    pub public_url: String,
+    /// How entries with the same score are ranked.
+    pub tie_break: TieBreak,
+}
+
+impl Default for LeaderboardSettings {
+    fn default() -> Self {
+        Self {
+            excluded_usernames: vec!["vilovshka".to_owned(), "flawrite".to_owned()],
+            display_version: env!("CARGO_PKG_VERSION").to_owned(),
+            public_url: "http://localhost:8080".to_owned(),
+            tie_break: TieBreak::Shared,
+        }
+    }
+}
+
+impl LeaderboardSettings {
+    /// `public_url` without a trailing slash.
+    pub fn public_url(&self) -> &str {
+        self.public_url.trim_end_matches('/')
+    }
+}
+
+/// Outgoing requests to Medium.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpSettings {
+    pub user_agent: String,
+    pub connect_timeout_seconds: u64,
+    pub request_timeout_seconds: u64,
+    /// Sustained request rate of the token bucket.
+    pub requests_per_second: f64,
+    /// Requests allowed in a row before the rate applies.
+    pub burst: u32,
+    /// Retries after a 429 or 503 before giving up on a request, at most 10.
+    pub max_retries: u32,
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            user_agent: concat!(
+                "medium_leaderboard/",
+                env!("CARGO_PKG_VERSION"),
+                " (+https://github.com/simonjung1603/medium_leaderboard)"
+            )
+            .to_owned(),
+            connect_timeout_seconds: 10,
+            request_timeout_seconds: 30,
+            requests_per_second: 1.0,
+            burst: 5,
+            max_retries: 3,
+        }
+    }
+}
+
+/// Raw Medium responses kept in `api_responses`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ArchiveSettings {
+    pub enabled: bool,
+    pub retention_days: u32,
+    /// Serve every Medium request from the archive instead of the network.
+    pub replay: bool,
+    /// In replay mode, ignore responses fetched after this point.
+    pub replay_as_of: Option<chrono::DateTime<chrono::Utc>>,
+    /// Environment variable with the url of the database copy used in replay mode. Replays never
+    /// run against `DATABASE_URL`.
+    pub replay_database_env: String,
+}
+
+impl Default for ArchiveSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention_days: 14,
+            replay: false,
+            replay_as_of: None,
+            replay_database_env: "REPLAY_DATABASE_URL".to_owned(),
+        }
+    }
+}
+
+/// Webhooks announcing leaderboard events.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationSettings {
+    pub targets: Vec<WebhookTarget>,
+    /// Clap counts announced when a submission passes them.
+    pub clap_milestones: Vec<i32>,
+    /// Message per event, replaces the built-in one.
+    pub templates: HashMap<NotificationEvent, String>,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            targets: Vec::new(),
+            clap_milestones: vec![100, 250, 500, 1000],
+            templates: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookKind {
+    Discord,
+    Slack,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookTarget {
+    pub name: String,
+    pub kind: WebhookKind,
+    /// Webhook urls are secrets, prefer `url_env` outside of development.
+    pub url: Option<String>,
+    /// Environment variable holding the webhook url.
+    pub url_env: Option<String>,
+    /// Events sent to this target, all if empty.
+    #[serde(default)]
+    pub events: Vec<NotificationEvent>,
+    /// Message per event, replaces the one in `notifications.templates`.
+    #[serde(default)]
+    pub templates: HashMap<NotificationEvent, String>,
+}
+
+impl ArchiveSettings {
+    /// The database to replay against, which has to differ from the live one.
+    pub fn replay_database_url(&self, live_database_url: &str) -> anyhow::Result<String> {
+        let url = std::env::var(&self.replay_database_env)
+            .map_err(|_| anyhow!("Replay mode needs a copy of the database in {}", self.replay_database_env))?;
+        if url == live_database_url {
+            bail!("{} must not point to the live database", self.replay_database_env);
+        }
+        Ok(url)
+    }
+}
+
+impl WebhookTarget {
+    pub fn url(&self) -> anyhow::Result<String> {
+        match (&self.url, &self.url_env) {
+            (Some(url), _) => Ok(url.clone()),
+            (None, Some(var)) => std::env::var(var).map_err(|_| anyhow!("{} is not set", var)),
+            (None, None) => bail!("Webhook {} has no url", self.name),
+        }
+    }
+
+    pub fn wants(&self, event: NotificationEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+/// Daily digests for subscribed authors.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EmailSettings {
+    pub enabled: bool,
+    pub from: String,
+    pub transport: MailTransport,
+    /// Where the `file` transport writes its `.eml` files.
+    pub mail_dir: String,
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    pub smtp_username: String,
+    pub smtp_password: String,
+    /// Confirmation links one email address may be sent per hour.
+    pub subscriptions_per_hour: i64,
+    /// Confirmation links one IP address may request per hour.
+    pub subscriptions_per_ip_per_hour: i64,
+}
+
+impl Default for EmailSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            from: "Medium Leaderboard <leaderboard@localhost>".to_owned(),
+            transport: MailTransport::File,
+            mail_dir: "target/mail".to_owned(),
+            smtp_host: String::new(),
+            smtp_port: 587,
+            smtp_username: String::new(),
+            smtp_password: String::new(),
+            subscriptions_per_hour: 3,
+            subscriptions_per_ip_per_hour: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MailTransport {
+    /// STARTTLS to `smtp_host`.
+    Smtp,
+    /// Writes every email to `mail_dir` instead of sending it, for development.
+    File,
+}
+
+/// The judges of the contest and what they score. How much the jury counts is set by the
+/// `blend` scoring of the contest.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JurySettings {
+    pub criteria: Vec<JuryCriterion>,
+    pub judges: Vec<JudgeSettings>,
+}
+
+impl Default for JurySettings {
+    fn default() -> Self {
+        Self {
+            criteria: ["Originality", "Craft", "Theme fit"]
+                .into_iter()
+                .map(|name| JuryCriterion {
+                    name: name.to_owned(),
+                    ..Default::default()
+                })
+                .collect(),
+            judges: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JudgeSettings {
+    pub name: String,
+    /// Environment variable holding the judge's login token.
+    pub token_env: String,
+}
+
+/// Readers' choice voting. Readers log in with an emailed link, so `email` must be enabled too.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct VotingSettings {
+    pub enabled: bool,
+    pub votes_per_category: i64,
+    /// Votes a reader may cast per hour.
+    pub votes_per_hour: i64,
+    /// Votes one IP address may cast per hour, readers behind the same router share it.
+    pub votes_per_ip_per_hour: i64,
+    /// Votes of further readers from the same IP address are flagged and not counted.
+    pub max_readers_per_ip: i64,
+    /// Votes of a reader using more IP addresses are flagged and not counted.
+    pub max_ips_per_reader: i64,
+    pub session_days: i64,
+    /// Login links one email address may be sent per hour.
+    pub logins_per_hour: i64,
+    /// Login links one IP address may request per hour.
+    pub logins_per_ip_per_hour: i64,
+    /// Proxies in front of the app that append to `X-Forwarded-For`. The client address is the
+    /// entry this many places from the right, anything further left is set by the client. With 0
+    /// the header is ignored and the address of the connection is used. Also applies to the
+    /// digest subscription form.
+    pub trusted_proxies: usize,
+}
+
+impl Default for VotingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            votes_per_category: 3,
+            votes_per_hour: 10,
+            votes_per_ip_per_hour: 30,
+            max_readers_per_ip: 5,
+            max_ips_per_reader: 5,
+            session_days: 30,
+            logins_per_hour: 3,
+            logins_per_ip_per_hour: 10,
+            trusted_proxies: 1,
+        }
+    }
+}
+
+/// Overrides the `job_schedules` row of a job.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JobSettings {
+    pub interval_seconds: Option<u64>,
+    pub enabled: Option<bool>,
+}
+
+impl Settings {
+    /// Reads `settings.toml` (or the file in `SETTINGS_FILE`) if present, applies `LEADERBOARD__`
+    /// environment overrides and validates the result.
+    pub fn load() -> anyhow::Result<Settings> {
+        let path = std::env::var("SETTINGS_FILE").unwrap_or_else(|_| SETTINGS_FILE.to_owned());
+        let mut table = if Path::new(&path).exists() {
+            toml::from_str::<toml::Table>(&std::fs::read_to_string(&path)?)
+                .map_err(|err| anyhow!("Error parsing {}: {}", path, err))?
+        } else {
+            toml::Table::new()
+        };
+
+        let typed_overrides = apply_env_overrides(&mut table, std::env::vars());
+
+        let settings = deserialize_settings(table, typed_overrides)?;
+        settings.validate()?;
+
+        Ok(settings)
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for (name, url) in [
+            ("medium.feed_url", &self.medium.feed_url),
+            ("medium.graphql_endpoint", &self.medium.graphql_endpoint),
+            ("leaderboard.public_url", &self.leaderboard.public_url),
+        ] {
+            let url = reqwest::Url::parse(url).map_err(|err| anyhow!("{} is not a valid url: {}", name, err))?;
+            if url.scheme() != "https" && url.scheme() != "http" {
+                bail!("{} must be an http(s) url", name);
+            }
+        }
+
+        if self.contest.slug.is_empty()
+            || self.contest.slug.chars().any(|c| !c.is_ascii_alphanumeric() && c != '-')
+        {
+            bail!("contest.slug must be non-empty and only contain letters, digits and dashes");
+        }
+        if self.contest.title.trim().is_empty() {
+            bail!("contest.title must not be empty");
+        }
+        if let (Some(starts_at), Some(ends_at)) = (self.contest.starts_at, self.contest.ends_at) {
+            if ends_at <= starts_at {
+                bail!("contest.ends_at must be after contest.starts_at");
+            }
+        }
+
+        if let Some(scoring) = &self.contest.scoring {
+            crate::server::scoring::validate(scoring).map_err(|err| anyhow!("contest.scoring: {}", err))?;
+        }
+
+        if self.http.requests_per_second.is_nan() || self.http.requests_per_second <= 0.0 {
+            bail!("http.requests_per_second must be positive");
+        }
+        if self.http.request_timeout_seconds == 0 || self.http.connect_timeout_seconds == 0 {
+            bail!("http timeouts must be positive");
+        }
+        if self.http.max_retries > crate::server::http::MAX_RETRIES {
+            bail!("http.max_retries must be at most {}", crate::server::http::MAX_RETRIES);
+        }
+
+        if self.archive.retention_days == 0 {
+            bail!("archive.retention_days must be positive");
+        }
+
+        for (index, target) in self.notifications.targets.iter().enumerate() {
+            if target.name.trim().is_empty() {
+                bail!("notifications.targets[{}].name must not be empty", index);
+            }
+            if self.notifications.targets[..index].iter().any(|other| other.name == target.name) {
+                bail!("notifications.targets[{}].name {} is used twice", index, target.name);
+            }
+            if target.url.is_none() && target.url_env.is_none() {
+                bail!("notifications.targets[{}] needs url or url_env", index);
+            }
+            if let Some(url) = &target.url {
+                reqwest::Url::parse(url)
+                    .map_err(|err| anyhow!("notifications.targets[{}].url is not a valid url: {}", index, err))?;
+            }
+        }
+
+        if self.email.enabled {
+            self.email
+                .from
+                .parse::<lettre::message::Mailbox>()
+                .map_err(|err| anyhow!("email.from is not a valid address: {}", err))?;
+            if self.email.transport == MailTransport::Smtp && self.email.smtp_host.is_empty() {
+                bail!("email.smtp_host must be set for the smtp transport");
+            }
+            for (name, value) in [
+                ("subscriptions_per_hour", self.email.subscriptions_per_hour),
+                ("subscriptions_per_ip_per_hour", self.email.subscriptions_per_ip_per_hour),
+            ] {
+                if value <= 0 {
+                    bail!("email.{} must be positive", name);
+                }
+            }
+        }
+
+        for (index, criterion) in self.jury.criteria.iter().enumerate() {
+            if criterion.name.trim().is_empty() {
+                bail!("jury.criteria[{}].name must not be empty", index);
+            }
+            if self.jury.criteria[..index].iter().any(|other| other.name == criterion.name) {
+                bail!("jury.criteria[{}].name {} is used twice", index, criterion.name);
+            }
+            if criterion.max <= 0 || criterion.weight.is_nan() || criterion.weight <= 0.0 {
+                bail!("jury.criteria[{}] needs a positive max and weight", index);
+            }
+        }
+        for (index, judge) in self.jury.judges.iter().enumerate() {
+            if judge.name.trim().is_empty() || judge.token_env.is_empty() {
+                bail!("jury.judges[{}] needs a name and token_env", index);
+            }
+            if self.jury.judges[..index].iter().any(|other| other.name == judge.name) {
+                bail!("jury.judges[{}].name {} is used twice", index, judge.name);
+            }
+        }
+
+        if self.voting.enabled {
+            if !self.email.enabled {
+                bail!("voting needs email to be enabled, readers log in with an emailed link");
+            }
+            for (name, value) in [
+                ("votes_per_category", self.voting.votes_per_category),
+                ("votes_per_hour", self.voting.votes_per_hour),
+                ("votes_per_ip_per_hour", self.voting.votes_per_ip_per_hour),
+                ("max_readers_per_ip", self.voting.max_readers_per_ip),
+                ("max_ips_per_reader", self.voting.max_ips_per_reader),
+                ("session_days", self.voting.session_days),
+                ("logins_per_hour", self.voting.logins_per_hour),
+                ("logins_per_ip_per_hour", self.voting.logins_per_ip_per_hour),
+            ] {
+                if value <= 0 {
+                    bail!("voting.{} must be positive", name);
+                }
+            }
+        }
+
+        for (name, job) in &self.jobs {
+            if job.interval_seconds == Some(0) {
+                bail!("jobs.{}.interval_seconds must be positive", name);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn job(&self, name: &str) -> JobSettings {
+        self.jobs.get(name).cloned().unwrap_or_default()
+    }
+
+    /// The built-in interval of a job, used when `job_schedules` has no row for it.
+    pub fn default_job_interval(&self, name: &str) -> std::time::Duration {
+        let seconds = DEFAULT_JOB_INTERVALS
+            .iter()
+            .find(|(job, _)| *job == name)
+            .map_or(FALLBACK_JOB_INTERVAL, |(_, seconds)| *seconds);
+        std::time::Duration::from_secs(seconds)
+    }
+}
+
+/// Applies the `LEADERBOARD__` variables to `table`. Returns the raw values of the overrides
+/// that were not taken as strings, by dotted path, see [`deserialize_settings`].
+fn apply_env_overrides(
+    table: &mut toml::Table,
+    vars: impl Iterator<Item = (String, String)>,
+) -> HashMap<String, String> {
+    let mut typed_overrides = HashMap::new();
+    for (key, value) in vars {
+        let Some(path) = key.strip_prefix(ENV_PREFIX) else {
+            continue;
+        };
+        let path: Vec<String> = path.split("__").map(|segment| segment.to_ascii_lowercase()).collect();
+        if path.is_empty() {
+            continue;
+        }
+
+        let parsed = parse_env_value(&value);
+        if !parsed.is_str() {
+            typed_overrides.insert(path.join("."), value);
+        }
+        insert_at(table, &path, parsed);
+    }
+    typed_overrides
+}
+
+fn insert_at(table: &mut toml::Table, path: &[String], value: toml::Value) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+
+    let mut current = table;
+    for parent in parents {
+        let entry = current
+            .entry(parent.clone())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if !entry.is_table() {
+            *entry = toml::Value::Table(toml::Table::new());
+        }
+        current = entry.as_table_mut().expect("Entry was just made a table");
+    }
+    current.insert(last.clone(), value);
+}
+
+/// Deserializes `table`. Whenever a parsed override has the wrong type for its field, e.g. a
+/// numeric password, it is retried as the string it was given as.
+fn deserialize_settings(mut table: toml::Table, mut typed_overrides: HashMap<String, String>) -> anyhow::Result<Settings> {
+    loop {
+        let err = match serde_path_to_error::deserialize::<_, Settings>(toml::Value::Table(table.clone())) {
+            Ok(settings) => return Ok(settings),
+            Err(err) => err,
+        };
+        let path = err.path().to_string();
+        let Some(value) = typed_overrides.remove(&path) else {
+            bail!("Invalid settings: {}", err);
+        };
+        let path: Vec<String> = path.split('.').map(str::to_owned).collect();
+        insert_at(&mut table, &path, toml::Value::String(value));
+    }
+}
+
+/// Numbers, booleans and arrays are parsed as TOML, everything else is taken as string.
+fn parse_env_value(value: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {}", value))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .filter(|parsed| {
+            matches!(
+                parsed,
+                toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) | toml::Value::Array(_)
+            )
+        })
+        .unwrap_or_else(|| toml::Value::String(value.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overrides(vars: &[(&str, &str)]) -> anyhow::Result<Settings> {
+        let mut table = toml::Table::new();
+        let typed_overrides = apply_env_overrides(
+            &mut table,
+            vars.iter().map(|(key, value)| (key.to_string(), value.to_string())),
+        );
+        deserialize_settings(table, typed_overrides)
+    }
+
+    #[test]
+    fn parses_typed_values() {
+        assert_eq!(parse_env_value("42"), toml::Value::Integer(42));
+        assert_eq!(parse_env_value("0.2"), toml::Value::Float(0.2));
+        assert_eq!(parse_env_value("true"), toml::Value::Boolean(true));
+        assert_eq!(
+            parse_env_value("[\"a\", \"b\"]"),
+            toml::Value::Array(vec![toml::Value::String("a".into()), toml::Value::String("b".into())])
+        );
+    }
+
+    #[test]
+    fn keeps_other_values_as_strings() {
+        assert_eq!(parse_env_value("Transformation"), toml::Value::String("Transformation".into()));
+        assert_eq!(parse_env_value("https://example.com"), toml::Value::String("https://example.com".into()));
+        assert_eq!(parse_env_value("\"quoted\""), toml::Value::String("\"quoted\"".into()));
+        assert_eq!(parse_env_value(""), toml::Value::String(String::new()));
+    }
+
+    #[test]
+    fn typed_overrides_reach_typed_fields() {
+        let settings = overrides(&[
+            ("LEADERBOARD__VOTING__VOTES_PER_CATEGORY", "5"),
+            ("LEADERBOARD__ARCHIVE__ENABLED", "true"),
+        ])
+        .unwrap();
+        assert_eq!(settings.voting.votes_per_category, 5);
+        assert!(settings.archive.enabled);
+    }
+
+    #[test]
+    fn number_like_overrides_of_string_fields_stay_strings() {
+        let settings = overrides(&[
+            ("LEADERBOARD__EMAIL__SMTP_PASSWORD", "123456"),
+            ("LEADERBOARD__LEADERBOARD__DISPLAY_VERSION", "0.2"),
+            ("LEADERBOARD__CONTEST__TITLE", "true"),
+            ("LEADERBOARD__VOTING__VOTES_PER_HOUR", "20"),
+        ])
+        .unwrap();
+        assert_eq!(settings.email.smtp_password, "123456");
+        assert_eq!(settings.leaderboard.display_version, "0.2");
+        assert_eq!(settings.contest.title, "true");
+        assert_eq!(settings.voting.votes_per_hour, 20);
+    }
+
+    #[test]
+    fn rejects_too_many_retries() {
+        let mut settings = Settings::default();
+        settings.http.max_retries = crate::server::http::MAX_RETRIES;
+        assert!(settings.validate().is_ok());
+
+        settings.http.max_retries = 40;
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn jobs_fall_back_to_their_default_interval() {
+        let settings = Settings::default();
+        assert_eq!(settings.default_job_interval("update_claps"), std::time::Duration::from_secs(15 * 60));
+        assert_eq!(settings.default_job_interval("unknown_job"), std::time::Duration::from_secs(FALLBACK_JOB_INTERVAL));
+    }
+
+    #[test]
+    fn wrong_types_are_still_rejected() {
+        assert!(overrides(&[("LEADERBOARD__VOTING__VOTES_PER_HOUR", "many")]).is_err());
+        assert!(overrides(&[("LEADERBOARD__MEDIUM__UNKNOWN_KEY", "1")]).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::models::{Category, Contest};
+use crate::server::ServerState;
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use chrono::{DateTime, Local};
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use dioxus::logger::tracing;
+
+/// `/sitemap.xml` with every contest, category, submission and author page, and a `/robots.txt`
+/// pointing to it.
+pub fn routes(state: ServerState) -> Router {
+    Router::new()
+        .route("/sitemap.xml", get(sitemap))
+        .route("/robots.txt", get(robots))
+        .with_state(state)
+}
+
+async fn robots(State(state): State<ServerState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        format!(
+            "User-agent: *\nDisallow: /admin\nDisallow: /jury\nDisallow: /readers/\nDisallow: /api/\nDisallow: /embed/\n\nSitemap: {}/sitemap.xml\n",
+            state.settings.leaderboard.public_url()
+        ),
+    )
+}
+
+async fn sitemap(State(state): State<ServerState>) -> Response {
+    let public_url = state.settings.leaderboard.public_url().to_owned();
+    let pages = tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<(String, Option<DateTime<Local>>)>> {
+        let mut connection = state.pool.get()?;
+        load_pages(&mut connection, &state.settings.leaderboard.excluded_usernames)
+    })
+    .await;
+
+    let pages = match pages {
+        Ok(Ok(pages)) => pages,
+        Ok(Err(err)) => {
+            tracing::error!("Building sitemap failed: {}", err);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+        Err(err) => {
+            tracing::error!("Building sitemap failed: {}", err);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let mut body = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for (path, last_modified) in pages {
+        body.push_str(&format!("<url><loc>{}{}</loc>", public_url, escape(&path)));
+        if let Some(last_modified) = last_modified {
+            body.push_str(&format!("<lastmod>{}</lastmod>", last_modified.format("%Y-%m-%d")));
+        }
+        body.push_str("</url>\n");
+    }
+    body.push_str("</urlset>\n");
+
+    (
+        [
+            (header::CONTENT_TYPE, "application/xml; charset=utf-8"),
+            (header::CACHE_CONTROL, "public, max-age=3600"),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Paths of all public pages, with the time their content last changed where known.
+fn load_pages(
+    connection: &mut PgConnection,
+    excluded_usernames: &[String],
+) -> anyhow::Result<Vec<(String, Option<DateTime<Local>>)>> {
+    use crate::db::contests::dsl as dslc;
+    use crate::db::submissions::dsl;
+
+    let contests = dslc::contests
+        .order_by(dslc::starts_at.desc())
+        .select(Contest::as_select())
+        .load(connection)?;
+    let submissions = dsl::submissions
+        .filter(dsl::username.ne_all(excluded_usernames))
+        .order_by(dsl::clap_count_last_updated_at.desc())
+        .select((
+            dsl::guid,
+            dsl::contest_id,
+            dsl::category,
+            dsl::author_id,
+            dsl::clap_count_last_updated_at,
+        ))
+        .load::<(String, i32, Category, Option<String>, DateTime<Local>)>(connection)?;
+
+    let last_change = |contest_id: i32, category: Option<Category>| {
+        submissions
+            .iter()
+            .filter(|(_, id, entry_category, _, _)| {
+                *id == contest_id && category.is_none_or(|category| category == *entry_category)
+            })
+            .map(|(_, _, _, _, updated_at)| *updated_at)
+            .max()
+    };
+
+    let mut pages = vec![
+        ("/".to_owned(), submissions.first().map(|(_, _, _, _, updated_at)| *updated_at)),
+        ("/statistics".to_owned(), None),
+    ];
+    for contest in &contests {
+        pages.push((format!("/contest/{}", contest.slug), last_change(contest.id, None)));
+        pages.push((format!("/history/{}", contest.slug), last_change(contest.id, None)));
+        for category in Category::ALL.into_iter().filter(|category| *category != Category::None) {
+            pages.push((
+                format!("/contest/{}/{}", contest.slug, category.slug()),
+                last_change(contest.id, Some(category)),
+            ));
+        }
+    }
+    for (guid, _, _, _, updated_at) in &submissions {
+        pages.push((format!("/submission/{}", guid), Some(*updated_at)));
+    }
+
+    let mut authors: Vec<&String> = submissions.iter().filter_map(|(_, _, _, author_id, _)| author_id.as_ref()).collect();
+    authors.sort();
+    authors.dedup();
+    for author_id in authors {
+        pages.push((format!("/author/{}", author_id), None));
+    }
+
+    Ok(pages)
+}
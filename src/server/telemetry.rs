@@ -0,0 +1,80 @@
+use axum::extract::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+use metrics::{counter, gauge, histogram};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+const LATENCY_BUCKETS: &[f64] = &[0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+const JOB_DURATION_BUCKETS: &[f64] = &[1.0, 5.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1800.0];
+
+/// Installs the global Prometheus recorder. The returned handle renders `/metrics`.
+pub fn install_recorder() -> anyhow::Result<PrometheusHandle> {
+    Ok(PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Full("medium_request_duration_seconds".to_owned()),
+            LATENCY_BUCKETS,
+        )?
+        .set_buckets_for_metric(
+            Matcher::Full("server_fn_duration_seconds".to_owned()),
+            LATENCY_BUCKETS,
+        )?
+        .set_buckets_for_metric(
+            Matcher::Full("job_duration_seconds".to_owned()),
+            JOB_DURATION_BUCKETS,
+        )?
+        .install_recorder()?)
+}
+
+/// Records latency and failure of a request to Medium, labelled with `operation`.
+pub(crate) async fn track_medium_request<T, E>(
+    operation: &'static str,
+    request: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let started = Instant::now();
+    let result = request.await;
+
+    histogram!("medium_request_duration_seconds", "operation" => operation)
+        .record(started.elapsed().as_secs_f64());
+    if result.is_err() {
+        counter!("medium_request_errors_total", "operation" => operation).increment(1);
+    }
+
+    result
+}
+
+pub(crate) fn record_submissions_ingested(rows: usize) {
+    counter!("submissions_ingested_total").increment(rows as u64);
+}
+
+pub(crate) fn record_clap_update() {
+    counter!("clap_updates_total").increment(1);
+}
+
+pub(crate) fn record_job_run(job_name: &'static str, status: &'static str, duration: Duration) {
+    histogram!("job_duration_seconds", "job" => job_name, "status" => status)
+        .record(duration.as_secs_f64());
+}
+
+/// 1 while the live response of `operation` differs from its recorded fixture.
+pub(crate) fn record_schema_drift(operation: &'static str, drifted: bool) {
+    gauge!("graphql_schema_drift", "operation" => operation).set(if drifted { 1.0 } else { 0.0 });
+}
+
+/// Middleware measuring the latency of server functions. Other routes pass through untouched.
+pub async fn track_server_fn(request: Request, next: Next) -> Response {
+    let path = request.uri().path().to_owned();
+    if !path.starts_with("/api/") {
+        return next.run(request).await;
+    }
+
+    let started = Instant::now();
+    let response = next.run(request).await;
+    let status = response.status().as_u16().to_string();
+
+    histogram!("server_fn_duration_seconds", "path" => path, "status" => status)
+        .record(started.elapsed().as_secs_f64());
+
+    response
+}
@@ -0,0 +1,484 @@
+use crate::models::{Category, InsertVote, Reader, ReadersChoiceEntry, Submission, Vote};
+use crate::server::email::Mailer;
+use crate::server::settings::Settings;
+use crate::server::{current_contest, ServerState};
+use anyhow::bail;
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use axum::routing::get;
+use axum::Router;
+use diesel::dsl::{count_distinct, count_star, sql};
+use diesel::sql_types::{Nullable, Text};
+use diesel::{
+    Connection, ExpressionMethods, Insertable, OptionalExtension, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper,
+};
+use dioxus::logger::tracing;
+use lettre::message::Mailbox;
+use lettre::Message;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+const SESSION_COOKIE: &str = "reader_session";
+/// Login links expire after an hour.
+const LOGIN_LINK_MINUTES: i64 = 60;
+/// A new login link can be requested once a minute.
+const LOGIN_REQUEST_INTERVAL_SECONDS: i64 = 60;
+
+/// `/readers/login/{token}` is the emailed login link. Like the unsubscribe link it asks for a
+/// click, so that link scanners do not use it up.
+pub fn routes(state: ServerState) -> Router {
+    Router::new()
+        .route("/readers/login/:token", get(login_page).post(login))
+        .with_state(state)
+}
+
+/// Sends `email` a login link, creating the reader on the first login. Requests beyond the hourly
+/// limits for the address or for `ip` are refused, so the form can not be used to flood inboxes.
+/// Without a known `ip` only the limit for the address applies.
+pub(crate) async fn request_login(state: &ServerState, email: &str, ip: Option<&str>) -> anyhow::Result<()> {
+    use crate::db::login_requests::dsl as dsll;
+    use crate::db::readers::dsl;
+    let voting = &state.settings.voting;
+    if !voting.enabled {
+        bail!("Readers' choice voting is not enabled.");
+    }
+
+    let mailbox: Mailbox = email.trim().parse().map_err(|_| anyhow::anyhow!("{} is not a valid email address", email))?;
+    let address = mailbox.email.to_string().to_lowercase();
+    let mut connection = state.pool.get()?;
+
+    let an_hour_ago = chrono::Local::now() - chrono::TimeDelta::hours(1);
+    diesel::delete(dsll::login_requests.filter(dsll::created_at.lt(an_hour_ago))).execute(&mut connection)?;
+    let recent_for_address: i64 = dsll::login_requests
+        .filter(dsll::email.eq(&address))
+        .count()
+        .get_result(&mut connection)?;
+    let recent_from_ip: i64 = match ip {
+        Some(ip) => dsll::login_requests
+            .filter(dsll::ip.eq(ip))
+            .count()
+            .get_result(&mut connection)?,
+        None => 0,
+    };
+    if recent_for_address >= voting.logins_per_hour || recent_from_ip >= voting.logins_per_ip_per_hour {
+        bail!("Too many login requests, please try again later.");
+    }
+
+    let existing = dsl::readers
+        .filter(dsl::email.eq(&address))
+        .select(Reader::as_select())
+        .first(&mut connection)
+        .optional()?;
+    if let Some(requested_at) = existing.and_then(|reader| reader.login_requested_at) {
+        if (chrono::Local::now() - requested_at).num_seconds() < LOGIN_REQUEST_INTERVAL_SECONDS {
+            bail!("A login link was just sent, please check your inbox.");
+        }
+    }
+
+    let reader = diesel::insert_into(dsl::readers)
+        .values((
+            dsl::email.eq(&address),
+            dsl::login_token.eq(sql::<Nullable<Text>>("gen_random_uuid()::text")),
+            dsl::login_requested_at.eq(chrono::Local::now()),
+        ))
+        .on_conflict(dsl::email)
+        .do_update()
+        .set((
+            dsl::login_token.eq(sql::<Nullable<Text>>("gen_random_uuid()::text")),
+            dsl::login_requested_at.eq(chrono::Local::now()),
+        ))
+        .returning(Reader::as_returning())
+        .get_result(&mut connection)?;
+    diesel::insert_into(dsll::login_requests)
+        .values((dsll::email.eq(&address), dsll::ip.eq(ip)))
+        .execute(&mut connection)?;
+
+    let message = Message::builder()
+        .from(state.settings.email.from.parse()?)
+        .to(mailbox)
+        .subject("Your readers' choice login link")
+        .body(format!(
+            "Log in to vote for your favorite entries: {}/readers/login/{}\n\n\
+             The link works for an hour. If this was not you, ignore this email.\n",
+            state.settings.leaderboard.public_url(),
+            reader.login_token.unwrap_or_default()
+        ))?;
+    Mailer::new(&state.settings.email)?.send(message).await?;
+    tracing::info!("Sent login link to reader {}.", reader.id);
+
+    Ok(())
+}
+
+async fn login_page(Path(token): Path<String>) -> Html<String> {
+    let token: String = token.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-').collect();
+    Html(format!(
+        "<!DOCTYPE html><html><body style=\"font-family: sans-serif\">\
+         <form method=\"post\" action=\"/readers/login/{}\">\
+         <p>Log in to vote for the readers' choice?</p>\
+         <button type=\"submit\">Log in</button>\
+         </form></body></html>",
+        token
+    ))
+}
+
+async fn login(State(state): State<ServerState>, Path(token): Path<String>) -> Response {
+    use crate::db::readers::dsl;
+    let settings = state.settings.clone();
+
+    let reader = tokio::task::spawn_blocking(move || -> anyhow::Result<Option<Reader>> {
+        let mut connection = state.pool.get()?;
+        Ok(diesel::update(
+            dsl::readers
+                .filter(dsl::login_token.eq(&token))
+                .filter(dsl::login_requested_at.gt(chrono::Local::now() - chrono::TimeDelta::minutes(LOGIN_LINK_MINUTES))),
+        )
+        .set((
+            dsl::login_token.eq(None::<String>),
+            dsl::verified_at.eq(sql::<Nullable<diesel::sql_types::Timestamptz>>("COALESCE(verified_at, CURRENT_TIMESTAMP)")),
+            dsl::session_token.eq(sql::<Nullable<Text>>("COALESCE(session_token, gen_random_uuid()::text)")),
+        ))
+        .returning(Reader::as_returning())
+        .get_result(&mut connection)
+        .optional()?)
+    })
+    .await;
+
+    match reader {
+        Ok(Ok(Some(reader))) => {
+            let secure = if settings.leaderboard.public_url().starts_with("https://") { "; Secure" } else { "" };
+            let cookie = format!(
+                "{}={}; Path=/; Max-Age={}; HttpOnly; SameSite=Lax{}",
+                SESSION_COOKIE,
+                reader.session_token.unwrap_or_default(),
+                settings.voting.session_days * 86400,
+                secure
+            );
+            ([(header::SET_COOKIE, cookie)], Redirect::to("/readers-choice")).into_response()
+        }
+        Ok(Ok(None)) => (StatusCode::NOT_FOUND, Html("This login link is not valid anymore.".to_owned())).into_response(),
+        Ok(Err(err)) => {
+            tracing::error!("Reader login failed: {}", err);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+        Err(err) => {
+            tracing::error!("Reader login failed: {}", err);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+fn session_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == SESSION_COOKIE)
+        .map(|(_, value)| value.to_owned())
+}
+
+/// The logged-in reader of the request.
+pub(crate) fn current_reader(connection: &mut PgConnection, headers: &HeaderMap) -> anyhow::Result<Option<Reader>> {
+    use crate::db::readers::dsl;
+    let Some(token) = session_token(headers) else {
+        return Ok(None);
+    };
+
+    Ok(dsl::readers
+        .filter(dsl::session_token.eq(&token))
+        .filter(dsl::verified_at.is_not_null())
+        .select(Reader::as_select())
+        .first(connection)
+        .optional()?)
+}
+
+/// The address the request came from, `None` if it is not known. Each of the `trusted_proxies`
+/// in front of the app appends the address it was called from to `X-Forwarded-For`, entries
+/// further left come from the client and may be made up. Requests without the header reached the
+/// app directly, from `peer`.
+pub(crate) fn client_ip(headers: &HeaderMap, peer: Option<SocketAddr>, trusted_proxies: usize) -> Option<String> {
+    let forwarded: Vec<&str> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty())
+        .collect();
+    if trusted_proxies == 0 || forwarded.is_empty() {
+        return peer.map(|peer| peer.ip().to_string());
+    }
+
+    forwarded
+        .len()
+        .checked_sub(trusted_proxies)
+        .and_then(|index| forwarded.get(index))
+        .map(|ip| (*ip).to_owned())
+}
+
+/// Casts a vote of `reader` for `guid`. Votes that look like ballot stuffing are stored
+/// flagged, so they count towards the limits but not the board. Without a known `ip` the
+/// limits and checks per address are skipped, rather than all such readers sharing one.
+pub(crate) fn vote(
+    connection: &mut PgConnection,
+    settings: &Settings,
+    reader: &Reader,
+    ip: Option<&str>,
+    guid: &str,
+) -> anyhow::Result<()> {
+    // The reader's row stays locked until the vote is stored, so concurrent votes of one reader
+    // can not all pass the limits before any of them is counted.
+    connection.transaction(|connection| {
+        use crate::db::readers::dsl as dslr;
+        dslr::readers
+            .find(reader.id)
+            .select(dslr::id)
+            .for_update()
+            .first::<i32>(connection)?;
+
+        cast_vote(connection, settings, reader, ip, guid)
+    })
+}
+
+fn cast_vote(
+    connection: &mut PgConnection,
+    settings: &Settings,
+    reader: &Reader,
+    ip: Option<&str>,
+    guid: &str,
+) -> anyhow::Result<()> {
+    use crate::db::submissions::dsl as dsls;
+    use crate::db::votes::dsl;
+    let voting = &settings.voting;
+
+    let contest = current_contest(connection, settings)?;
+    if contest.ends_at.is_some_and(|ends_at| ends_at <= chrono::Local::now()) {
+        bail!("Voting has closed.");
+    }
+    let Some(submission) = dsls::submissions
+        .filter(dsls::guid.eq(guid))
+        .filter(dsls::contest_id.eq(contest.id))
+        .select(Submission::as_select())
+        .first(connection)
+        .optional()?
+    else {
+        bail!("This is not an entry of {}.", contest.title);
+    };
+    if submission.category == Category::None || settings.leaderboard.excluded_usernames.contains(&submission.username) {
+        bail!("This entry can not be voted for.");
+    }
+
+    let an_hour_ago = chrono::Local::now() - chrono::TimeDelta::hours(1);
+    let recent_by_reader: i64 = dsl::votes
+        .filter(dsl::reader_id.eq(reader.id))
+        .filter(dsl::created_at.gt(an_hour_ago))
+        .count()
+        .get_result(connection)?;
+    let recent_by_ip: i64 = match ip {
+        Some(ip) => dsl::votes
+            .filter(dsl::ip.eq(ip))
+            .filter(dsl::created_at.gt(an_hour_ago))
+            .count()
+            .get_result(connection)?,
+        None => 0,
+    };
+    if recent_by_reader >= voting.votes_per_hour || recent_by_ip >= voting.votes_per_ip_per_hour {
+        bail!("Too many votes, please try again later.");
+    }
+
+    let in_category: i64 = dsl::votes
+        .filter(dsl::reader_id.eq(reader.id))
+        .filter(dsl::contest_id.eq(contest.id))
+        .filter(dsl::category.eq(submission.category))
+        .count()
+        .get_result(connection)?;
+    if in_category >= voting.votes_per_category {
+        bail!(
+            "You used all {} votes in {}. Take one back to vote for another entry.",
+            voting.votes_per_category,
+            submission.category.name()
+        );
+    }
+
+    let flag = match ip {
+        Some(ip) => fraud_flag(connection, settings, reader, contest.id, ip)?,
+        None => None,
+    };
+    if let Some(flag) = &flag {
+        tracing::warn!("Flagged vote of reader {} for {}: {}", reader.id, guid, flag);
+    }
+
+    InsertVote {
+        reader_id: reader.id,
+        guid: submission.guid,
+        contest_id: contest.id,
+        category: submission.category,
+        ip: ip.map(str::to_owned),
+        flag,
+    }
+    .insert_into(dsl::votes)
+    .on_conflict((dsl::reader_id, dsl::guid))
+    .do_nothing()
+    .execute(connection)?;
+
+    Ok(())
+}
+
+/// Why a vote from `ip` looks like ballot stuffing, if it does.
+fn fraud_flag(
+    connection: &mut PgConnection,
+    settings: &Settings,
+    reader: &Reader,
+    contest_id: i32,
+    ip: &str,
+) -> anyhow::Result<Option<String>> {
+    use crate::db::votes::dsl;
+    let voting = &settings.voting;
+
+    let other_readers_on_ip: i64 = dsl::votes
+        .filter(dsl::contest_id.eq(contest_id))
+        .filter(dsl::ip.eq(ip))
+        .filter(dsl::reader_id.ne(reader.id))
+        .select(count_distinct(dsl::reader_id))
+        .get_result(connection)?;
+    let other_ips_of_reader: i64 = dsl::votes
+        .filter(dsl::contest_id.eq(contest_id))
+        .filter(dsl::reader_id.eq(reader.id))
+        .filter(dsl::ip.ne(ip))
+        .select(count_distinct(dsl::ip))
+        .get_result(connection)?;
+
+    Ok(if other_readers_on_ip >= voting.max_readers_per_ip {
+        Some(format!("{} other readers voted from {}", other_readers_on_ip, ip))
+    } else if other_ips_of_reader >= voting.max_ips_per_reader {
+        Some(format!("Reader voted from {} other addresses", other_ips_of_reader))
+    } else {
+        None
+    })
+}
+
+/// Takes back a vote while voting is open.
+pub(crate) fn retract(connection: &mut PgConnection, settings: &Settings, reader: &Reader, guid: &str) -> anyhow::Result<()> {
+    use crate::db::votes::dsl;
+
+    let contest = current_contest(connection, settings)?;
+    if contest.ends_at.is_some_and(|ends_at| ends_at <= chrono::Local::now()) {
+        bail!("Voting has closed.");
+    }
+    diesel::delete(
+        dsl::votes
+            .filter(dsl::reader_id.eq(reader.id))
+            .filter(dsl::contest_id.eq(contest.id))
+            .filter(dsl::guid.eq(guid)),
+    )
+    .execute(connection)?;
+
+    Ok(())
+}
+
+/// Every categorized entry with its unflagged votes, most votes first.
+pub(crate) fn readers_choice(
+    connection: &mut PgConnection,
+    settings: &Settings,
+    contest_id: i32,
+) -> anyhow::Result<Vec<ReadersChoiceEntry>> {
+    use crate::db::submissions::dsl as dsls;
+    use crate::db::votes::dsl;
+
+    let counts: HashMap<String, i64> = dsl::votes
+        .filter(dsl::contest_id.eq(contest_id))
+        .filter(dsl::flag.is_null())
+        .group_by(dsl::guid)
+        .select((dsl::guid, count_star()))
+        .load::<(String, i64)>(connection)?
+        .into_iter()
+        .collect();
+
+    let mut entries: Vec<ReadersChoiceEntry> = dsls::submissions
+        .filter(dsls::contest_id.eq(contest_id))
+        .filter(dsls::category.ne(Category::None))
+        .filter(dsls::username.ne_all(&settings.leaderboard.excluded_usernames))
+        .select(Submission::as_select())
+        .load(connection)?
+        .into_iter()
+        .map(|submission| ReadersChoiceEntry {
+            votes: counts.get(&submission.guid).copied().unwrap_or_default(),
+            submission,
+        })
+        .collect();
+    entries.sort_by(|a, b| b.votes.cmp(&a.votes).then(a.submission.title.cmp(&b.submission.title)));
+
+    Ok(entries)
+}
+
+/// Guids the reader voted for in the contest.
+pub(crate) fn reader_votes(connection: &mut PgConnection, reader: &Reader, contest_id: i32) -> anyhow::Result<Vec<String>> {
+    use crate::db::votes::dsl;
+
+    Ok(dsl::votes
+        .filter(dsl::reader_id.eq(reader.id))
+        .filter(dsl::contest_id.eq(contest_id))
+        .select(dsl::guid)
+        .load(connection)?)
+}
+
+/// Votes held back by the fraud check, newest first, with the reader's email.
+pub(crate) fn flagged_votes(connection: &mut PgConnection, limit: i64) -> anyhow::Result<Vec<(Vote, String)>> {
+    use crate::db::readers::dsl as dslr;
+    use crate::db::votes::dsl;
+
+    Ok(dsl::votes
+        .inner_join(dslr::readers)
+        .filter(dsl::flag.is_not_null())
+        .order_by(dsl::created_at.desc())
+        .limit(limit)
+        .select((Vote::as_select(), dslr::email))
+        .load(connection)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn forwarded_for(values: &[&str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append("x-forwarded-for", HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    fn peer() -> Option<SocketAddr> {
+        Some("198.51.100.4:51234".parse().unwrap())
+    }
+
+    #[test]
+    fn takes_the_address_appended_by_the_proxy() {
+        let headers = forwarded_for(&["6.6.6.6, 203.0.113.7"]);
+
+        assert_eq!(client_ip(&headers, peer(), 1).as_deref(), Some("203.0.113.7"));
+    }
+
+    #[test]
+    fn skips_the_addresses_of_further_proxies() {
+        let headers = forwarded_for(&["6.6.6.6, 203.0.113.7", "10.0.0.2"]);
+
+        assert_eq!(client_ip(&headers, peer(), 2).as_deref(), Some("203.0.113.7"));
+    }
+
+    #[test]
+    fn falls_back_to_the_peer_address() {
+        assert_eq!(client_ip(&HeaderMap::new(), peer(), 1).as_deref(), Some("198.51.100.4"));
+        // Without proxies the header is made up by the client.
+        assert_eq!(client_ip(&forwarded_for(&["6.6.6.6"]), peer(), 0).as_deref(), Some("198.51.100.4"));
+    }
+
+    #[test]
+    fn unknown_addresses_are_none() {
+        assert_eq!(client_ip(&HeaderMap::new(), None, 1), None);
+        assert_eq!(client_ip(&forwarded_for(&["203.0.113.7"]), peer(), 2), None);
+    }
+}
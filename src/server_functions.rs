@@ -1,27 +1,455 @@
 use crate::models::*;
-use chrono::{DateTime, Local, TimeDelta};
+use chrono::{DateTime, Local};
 use dioxus::prelude::*;
-use std::ops::Add;
-use anyhow::anyhow;
-use dioxus::logger::tracing;
 
 #[cfg(feature = "server")]
 use crate::db::*;
 
+#[cfg(feature = "server")]
+use {
+    crate::server::settings::Settings, crate::server::ServerState, dioxus::logger::tracing, std::sync::Arc,
+};
+
+/// Entries shown by the embeddable leaderboard.
+#[cfg(feature = "server")]
+const EMBED_LENGTH: usize = 5;
+/// Moments of the animated clap chart.
+#[cfg(feature = "server")]
+const CLAP_RACE_FRAMES: usize = 60;
+/// Moments of the time travel playback, one per position of its slider.
+#[cfg(feature = "server")]
+const TIME_TRAVEL_FRAMES: usize = 101;
+/// Most authors a single hall of fame request returns.
+#[cfg(feature = "server")]
+const MAX_TOP_AUTHORS: i64 = 50;
+
 #[server(GetAllSubmissions)]
 pub async fn get_all_submissions() -> Result<Vec<Submission>, ServerFnError> {
     use crate::schema::submissions::dsl::*;
     let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
     let mut connection = pool.get()?;
-    let all_submissions = submissions.filter(username.ne_all(vec!["vilovshka", "flawrite"]))
+    let contest = crate::server::current_contest(&mut connection, &settings)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+    let all_submissions = submissions.filter(contest_id.eq(contest.id))
+        .filter(username.ne_all(&settings.leaderboard.excluded_usernames))
         .select(Submission::as_select())
         .order_by(clap_count.desc())
-        .load(&mut connection)
-        .expect("Error loading submissions.");
+        .load(&mut connection)?;
 
     Ok(all_submissions)
 }
 
+#[server(GetSiteInfo)]
+pub async fn get_site_info() -> Result<SiteInfo, ServerFnError> {
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let contest = crate::server::current_contest(&mut connection, &settings)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+
+    Ok(SiteInfo {
+        contest,
+        version: settings.leaderboard.display_version.clone(),
+        public_url: settings.leaderboard.public_url().to_owned(),
+        voting: settings.voting.enabled,
+    })
+}
+
+/// The leaders of one category, ranked with the contest's scoring strategy.
+#[server(GetEmbedLeaderboard)]
+pub async fn get_embed_leaderboard(contest_slug: String, category: Category) -> Result<(Contest, Vec<ScoredSubmission>), ServerFnError> {
+    use crate::db::contests::dsl as dslc;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let contest = dslc::contests
+        .filter(dslc::slug.eq(&contest_slug))
+        .select(Contest::as_select())
+        .first(&mut connection)?;
+
+    let leaders = crate::server::scoring::score_contest(&mut connection, &settings, &contest)
+        .map_err(|err| ServerFnError::new(err.to_string()))?
+        .into_iter()
+        .filter(|entry| entry.submission.category == category)
+        .take(EMBED_LENGTH)
+        .collect();
+
+    Ok((contest, leaders))
+}
+
+/// Entries of the contest, ranked and scored with its scoring strategy.
+#[server(GetContestStandings)]
+pub async fn get_contest_standings(contest_slug: String) -> Result<(Contest, Vec<ScoredSubmission>), ServerFnError> {
+    use crate::db::contests::dsl as dslc;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let contest = dslc::contests
+        .filter(dslc::slug.eq(&contest_slug))
+        .select(Contest::as_select())
+        .first(&mut connection)?;
+
+    let entries = crate::server::scoring::score_contest(&mut connection, &settings, &contest)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+
+    Ok((contest, entries))
+}
+
+/// Scores of the current contest, for the leaderboard on the home page.
+#[server(GetScoredSubmissions)]
+pub async fn get_scored_submissions() -> Result<(Contest, Vec<ScoredSubmission>), ServerFnError> {
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let contest = crate::server::current_contest(&mut connection, &settings)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+    let entries = crate::server::scoring::score_contest(&mut connection, &settings, &contest)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+
+    Ok((contest, entries))
+}
+
+#[server(SetContestScoring)]
+pub async fn set_contest_scoring(token: String, contest_slug: String, scoring: Scoring) -> Result<(), ServerFnError> {
+    use crate::db::contests::dsl;
+    crate::server::auth::require_admin(&token)?;
+    crate::server::scoring::validate(&scoring).map_err(|err| ServerFnError::new(err.to_string()))?;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let scoring_json = serde_json::to_string(&scoring)?;
+    if Ok(1) == diesel::update(dsl::contests.filter(dsl::slug.eq(&contest_slug))).set(dsl::scoring.eq(scoring_json)).execute(&mut connection) {
+        tracing::info!("Scoring of {} set to {:?}.", contest_slug, scoring);
+        return Ok(());
+    }
+
+    Err(ServerFnError::new(format!("Unknown contest {}", contest_slug)))
+}
+
+/// The ranking of the contest as it stood at `timestamp`, rebuilt from the clap history.
+#[server(GetLeaderboardAt)]
+pub async fn get_leaderboard_at(contest_slug: String, timestamp: DateTime<Local>) -> Result<Vec<ScoredSubmission>, ServerFnError> {
+    use crate::db::contests::dsl as dslc;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let contest = dslc::contests
+        .filter(dslc::slug.eq(&contest_slug))
+        .select(Contest::as_select())
+        .first(&mut connection)?;
+
+    crate::server::history::standings_at(&mut connection, &settings, &contest, timestamp)
+        .map_err(|err| ServerFnError::new(err.to_string()))
+}
+
+/// The rankings of the contest over its duration, for the time travel playback.
+#[server(GetTimeTravel)]
+pub async fn get_time_travel(contest_slug: String) -> Result<TimeTravel, ServerFnError> {
+    use crate::db::contests::dsl as dslc;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let contest = dslc::contests
+        .filter(dslc::slug.eq(&contest_slug))
+        .select(Contest::as_select())
+        .first(&mut connection)?;
+
+    crate::server::history::time_travel(&mut connection, &settings, &contest, TIME_TRAVEL_FRAMES)
+        .map_err(|err| ServerFnError::new(err.to_string()))
+}
+
+/// Clap counts of the current contest over its duration, for the animated clap chart.
+#[server(GetClapRace)]
+pub async fn get_clap_race() -> Result<ClapRace, ServerFnError> {
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let contest = crate::server::current_contest(&mut connection, &settings)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+
+    crate::server::history::clap_race(&mut connection, &settings, &contest, CLAP_RACE_FRAMES)
+        .map_err(|err| ServerFnError::new(err.to_string()))
+}
+
+#[server(GetJuryBallot)]
+pub async fn get_jury_ballot(token: String) -> Result<JuryBallot, ServerFnError> {
+    use crate::db::judge_scores::dsl;
+    use crate::db::submissions::dsl as dsls;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
+    let judge = crate::server::auth::require_judge(&settings, &token)?;
+    let mut connection = pool.get()?;
+
+    let contest = crate::server::current_contest(&mut connection, &settings)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+    let entries = dsls::submissions
+        .filter(dsls::contest_id.eq(contest.id))
+        .filter(dsls::category.ne(Category::None))
+        .filter(dsls::username.ne_all(&settings.leaderboard.excluded_usernames))
+        .order_by((dsls::category.asc(), dsls::title.asc()))
+        .select(Submission::as_select())
+        .load(&mut connection)?;
+    let scores = dsl::judge_scores
+        .filter(dsl::judge.eq(&judge))
+        .filter(dsl::guid.eq_any(entries.iter().map(|entry| entry.guid.clone()).collect::<Vec<_>>()))
+        .select(JudgeScore::as_select())
+        .load(&mut connection)?;
+
+    Ok(JuryBallot {
+        judge,
+        contest,
+        criteria: settings.jury.criteria.clone(),
+        entries,
+        scores,
+    })
+}
+
+/// Stores the judge's `(criterion, score, comment)` for one entry. Scores are final once the
+/// results are published.
+#[server(SubmitJudgeScores)]
+pub async fn submit_judge_scores(token: String, submission_guid: String, scores: Vec<(String, i32, String)>) -> Result<(), ServerFnError> {
+    use crate::db::judge_scores::dsl;
+    use crate::db::submissions::dsl as dsls;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
+    let judge = crate::server::auth::require_judge(&settings, &token)?;
+    let mut connection = pool.get()?;
+
+    let contest = crate::server::current_contest(&mut connection, &settings)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+    if contest.jury_published_at.is_some() {
+        return Err(ServerFnError::new("The jury results are already published"));
+    }
+    let entered = dsls::submissions
+        .filter(dsls::guid.eq(&submission_guid))
+        .filter(dsls::contest_id.eq(contest.id))
+        .count()
+        .get_result::<i64>(&mut connection)?;
+    if entered == 0 {
+        return Err(ServerFnError::new(format!("{} is not an entry of {}", submission_guid, contest.title)));
+    }
+
+    for (criterion, score, _) in &scores {
+        let Some(known) = settings.jury.criteria.iter().find(|known| known.name == *criterion) else {
+            return Err(ServerFnError::new(format!("Unknown criterion {}", criterion)));
+        };
+        if !(0..=known.max).contains(score) {
+            return Err(ServerFnError::new(format!("{} must be between 0 and {}", criterion, known.max)));
+        }
+    }
+
+    connection.transaction(|connection| {
+        for (criterion, score, comment) in scores {
+            InsertJudgeScore {
+                guid: submission_guid.clone(),
+                judge: judge.clone(),
+                criterion,
+                score,
+                comment: comment.trim().to_owned(),
+            }
+            .insert_into(dsl::judge_scores)
+            .on_conflict((dsl::guid, dsl::judge, dsl::criterion))
+            .do_update()
+            .set((
+                dsl::score.eq(diesel::upsert::excluded(dsl::score)),
+                dsl::comment.eq(diesel::upsert::excluded(dsl::comment)),
+                dsl::updated_at.eq(chrono::Local::now()),
+            ))
+            .execute(connection)?;
+        }
+        diesel::QueryResult::Ok(())
+    })?;
+
+    tracing::info!("{} scored {}.", judge, submission_guid);
+    Ok(())
+}
+
+/// Aggregated jury results of the contest, empty until they are published.
+#[server(GetJuryResults)]
+pub async fn get_jury_results(contest_slug: String) -> Result<Vec<JuryResult>, ServerFnError> {
+    use crate::db::contests::dsl as dslc;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let contest = dslc::contests
+        .filter(dslc::slug.eq(&contest_slug))
+        .select(Contest::as_select())
+        .first(&mut connection)?;
+    if contest.jury_published_at.is_none() {
+        return Ok(Vec::new());
+    }
+
+    let results = crate::server::jury::results(&mut connection, &settings, contest.id)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+
+    Ok(results.into_values().collect())
+}
+
+/// Individual scores and comments on one submission, empty until the results are published.
+#[server(GetJudgeScores)]
+pub async fn get_judge_scores(submission_guid: String) -> Result<Vec<JudgeScore>, ServerFnError> {
+    use crate::db::contests::dsl as dslc;
+    use crate::db::judge_scores::dsl;
+    use crate::db::submissions::dsl as dsls;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let scores = dsl::judge_scores
+        .inner_join(dsls::submissions.inner_join(dslc::contests))
+        .filter(dsl::guid.eq(&submission_guid))
+        .filter(dslc::jury_published_at.is_not_null())
+        .order_by((dsl::judge.asc(), dsl::criterion.asc()))
+        .select(JudgeScore::as_select())
+        .load(&mut connection)?;
+
+    Ok(scores)
+}
+
+#[server(GetSubmission)]
+pub async fn get_submission(submission_guid: String) -> Result<(Contest, Submission), ServerFnError> {
+    use crate::db::contests::dsl as dslc;
+    use crate::db::submissions::dsl as dsls;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let entry = dsls::submissions
+        .inner_join(dslc::contests)
+        .filter(dsls::guid.eq(&submission_guid))
+        .select((Contest::as_select(), Submission::as_select()))
+        .first::<(Contest, Submission)>(&mut connection)?;
+
+    Ok(entry)
+}
+
+#[server(GetAuthor)]
+pub async fn get_author(author_id: String) -> Result<(Author, Vec<(Contest, Submission)>), ServerFnError> {
+    use crate::db::authors::dsl as dsla;
+    use crate::db::contests::dsl as dslc;
+    use crate::db::submissions::dsl as dsls;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let author = dsla::authors
+        .find(&author_id)
+        .select(Author::as_select())
+        .first(&mut connection)?;
+
+    let entries = dsls::submissions
+        .inner_join(dslc::contests)
+        .filter(dsls::author_id.eq(&author_id))
+        .select((Contest::as_select(), Submission::as_select()))
+        .order_by((dslc::starts_at.desc(), dsls::clap_count.desc()))
+        .load::<(Contest, Submission)>(&mut connection)?;
+
+    Ok((author, entries))
+}
+
+#[server(SubscribeToDigest)]
+pub async fn subscribe_to_digest(username: String, email: String) -> Result<(), ServerFnError> {
+    let FromContext::<ServerState>(state) = extract().await?;
+    let headers: axum::http::HeaderMap = extract().await?;
+    let peer: Option<axum::extract::ConnectInfo<std::net::SocketAddr>> = extract().await.ok();
+    let ip = crate::server::voting::client_ip(&headers, peer.map(|peer| peer.0), state.settings.voting.trusted_proxies);
+
+    crate::server::email::subscribe(&state, &username, &email, ip.as_deref())
+        .await
+        .map_err(|err| ServerFnError::new(err.to_string()))
+}
+
+/// The readers' choice board of the current contest, with the ballot of the logged-in reader.
+#[server(GetReadersChoice)]
+pub async fn get_readers_choice() -> Result<ReadersChoice, ServerFnError> {
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
+    let headers: axum::http::HeaderMap = extract().await?;
+    let mut connection = pool.get()?;
+
+    let contest = crate::server::current_contest(&mut connection, &settings)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+    let entries = crate::server::voting::readers_choice(&mut connection, &settings, contest.id)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+    let reader = crate::server::voting::current_reader(&mut connection, &headers)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+    let my_votes = match &reader {
+        Some(reader) => crate::server::voting::reader_votes(&mut connection, reader, contest.id)
+            .map_err(|err| ServerFnError::new(err.to_string()))?,
+        None => Vec::new(),
+    };
+
+    Ok(ReadersChoice {
+        open: settings.voting.enabled && contest.ends_at.is_none_or(|ends_at| ends_at > Local::now()),
+        contest,
+        entries,
+        votes_per_category: settings.voting.votes_per_category,
+        reader: reader.map(|reader| reader.email),
+        my_votes,
+    })
+}
+
+#[server(RequestReaderLogin)]
+pub async fn request_reader_login(email: String) -> Result<(), ServerFnError> {
+    let FromContext::<ServerState>(state) = extract().await?;
+    let headers: axum::http::HeaderMap = extract().await?;
+    let peer: Option<axum::extract::ConnectInfo<std::net::SocketAddr>> = extract().await.ok();
+    let ip = crate::server::voting::client_ip(&headers, peer.map(|peer| peer.0), state.settings.voting.trusted_proxies);
+
+    crate::server::voting::request_login(&state, &email, ip.as_deref())
+        .await
+        .map_err(|err| ServerFnError::new(err.to_string()))
+}
+
+#[server(ReaderLogout)]
+pub async fn reader_logout() -> Result<(), ServerFnError> {
+    use crate::db::readers::dsl;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let headers: axum::http::HeaderMap = extract().await?;
+    let mut connection = pool.get()?;
+
+    if let Some(reader) = crate::server::voting::current_reader(&mut connection, &headers)
+        .map_err(|err| ServerFnError::new(err.to_string()))?
+    {
+        diesel::update(&reader)
+            .set(dsl::session_token.eq(None::<String>))
+            .execute(&mut connection)?;
+    }
+
+    Ok(())
+}
+
+/// Votes for `submission_guid`, or takes the vote back if `voted` is false.
+#[server(SetReaderVote)]
+pub async fn set_reader_vote(submission_guid: String, voted: bool) -> Result<(), ServerFnError> {
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
+    let headers: axum::http::HeaderMap = extract().await?;
+    let peer: Option<axum::extract::ConnectInfo<std::net::SocketAddr>> = extract().await.ok();
+    if !settings.voting.enabled {
+        return Err(ServerFnError::new("Readers' choice voting is not enabled"));
+    }
+    let mut connection = pool.get()?;
+
+    let reader = crate::server::voting::current_reader(&mut connection, &headers)
+        .map_err(|err| ServerFnError::new(err.to_string()))?
+        .ok_or(ServerFnError::new("Please log in to vote"))?;
+    let result = if voted {
+        let ip = crate::server::voting::client_ip(&headers, peer.map(|peer| peer.0), settings.voting.trusted_proxies);
+        crate::server::voting::vote(&mut connection, &settings, &reader, ip.as_deref(), &submission_guid)
+    } else {
+        crate::server::voting::retract(&mut connection, &settings, &reader, &submission_guid)
+    };
+
+    result.map_err(|err| ServerFnError::new(err.to_string()))
+}
+
 #[server(UpdateCategory)]
 pub async fn update_category(update_guid: String, update_category: Category) -> Result<(), ServerFnError> {
     use crate::schema::submissions::dsl::*;
@@ -38,22 +466,326 @@ pub async fn update_category(update_guid: String, update_category: Category) ->
 }
 
 #[server(GetLatestUpdateTime)]
-pub async fn get_latest_and_next_update_time() -> Result<(DateTime<Local>, DateTime<Local>), ServerFnError> {
-    use crate::schema::submissions::dsl::*;
+pub async fn get_latest_and_next_update_time() -> Result<(Option<DateTime<Local>>, Option<DateTime<Local>>), ServerFnError> {
+    use crate::server::scheduler::JobRegistry;
+    let FromContext::<ServerState>(state) = extract().await?;
+    let FromContext::<JobRegistry>(registry) = extract().await?;
+
+    let claps_job = registry
+        .overview(&state)
+        .map_err(|err| ServerFnError::new(err.to_string()))?
+        .into_iter()
+        .find(|job| job.name == "update_claps")
+        .ok_or(ServerFnError::new("Clap update job is not registered"))?;
+
+    let latest_update_time = claps_job
+        .last_run
+        .and_then(|run| run.finished_at.or(Some(run.started_at)));
+
+    Ok((latest_update_time, claps_job.next_run))
+}
+
+#[server(GetJobOverview)]
+pub async fn get_job_overview(token: String) -> Result<Vec<JobOverview>, ServerFnError> {
+    use crate::server::scheduler::JobRegistry;
+    crate::server::auth::require_admin(&token)?;
+    let FromContext::<ServerState>(state) = extract().await?;
+    let FromContext::<JobRegistry>(registry) = extract().await?;
+
+    registry
+        .overview(&state)
+        .map_err(|err| ServerFnError::new(err.to_string()))
+}
+
+#[server(TriggerJob)]
+pub async fn trigger_job(token: String, job_name: String) -> Result<(), ServerFnError> {
+    use crate::server::scheduler::JobRegistry;
+    crate::server::auth::require_admin(&token)?;
+    let FromContext::<ServerState>(state) = extract().await?;
+    let FromContext::<JobRegistry>(registry) = extract().await?;
+
+    let job = registry
+        .get(&job_name)
+        .ok_or(ServerFnError::new(format!("Unknown job {}", job_name)))?;
+    if job.is_running() {
+        return Err(ServerFnError::new(format!("Job {} is already running", job_name)));
+    }
+
+    tracing::info!("Manually triggered job {}.", job_name);
+    tokio::spawn(async move {
+        job.run(&state).await;
+    });
+
+    Ok(())
+}
+
+/// Jury results of the current contest, also before they are published.
+#[server(GetJuryOverview)]
+pub async fn get_jury_overview(token: String) -> Result<(Contest, Vec<(Submission, JuryResult)>), ServerFnError> {
+    use crate::db::submissions::dsl as dsls;
+    crate::server::auth::require_admin(&token)?;
     let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
     let mut connection = pool.get()?;
 
-    let latest_update_time = match submissions
-        .select(clap_count_last_updated_at)
-        .order_by(clap_count_last_updated_at.desc())
-        .first::<chrono::DateTime<chrono::Local>>(&mut connection)
-    {
-        Ok(db_time) => chrono::DateTime::from(db_time),
-        Err(_) => Local::now(),
+    let contest = crate::server::current_contest(&mut connection, &settings)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+    let mut results = crate::server::jury::results(&mut connection, &settings, contest.id)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+    let mut overview: Vec<(Submission, JuryResult)> = dsls::submissions
+        .filter(dsls::guid.eq_any(results.keys().cloned().collect::<Vec<_>>()))
+        .select(Submission::as_select())
+        .load(&mut connection)?
+        .into_iter()
+        .filter_map(|submission| results.remove(&submission.guid).map(|result| (submission, result)))
+        .collect();
+    overview.sort_by(|(_, a), (_, b)| b.score.total_cmp(&a.score));
+
+    Ok((contest, overview))
+}
+
+#[server(PublishJuryResults)]
+pub async fn publish_jury_results(token: String, contest_slug: String, published: bool) -> Result<(), ServerFnError> {
+    use crate::db::contests::dsl;
+    crate::server::auth::require_admin(&token)?;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let published_at = published.then(chrono::Local::now);
+    let updated = diesel::update(dsl::contests.filter(dsl::slug.eq(&contest_slug)))
+        .set(dsl::jury_published_at.eq(published_at))
+        .execute(&mut connection)?;
+    if updated == 0 {
+        return Err(ServerFnError::new(format!("Unknown contest {}", contest_slug)));
+    }
+
+    tracing::info!("Jury results of {} published: {}.", contest_slug, published);
+    Ok(())
+}
+
+#[server(GetFlaggedVotes)]
+pub async fn get_flagged_votes(token: String, limit: i64) -> Result<Vec<(Vote, String)>, ServerFnError> {
+    crate::server::auth::require_admin(&token)?;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let mut connection = pool.get()?;
+
+    crate::server::voting::flagged_votes(&mut connection, limit)
+        .map_err(|err| ServerFnError::new(err.to_string()))
+}
+
+#[server(GetIngestErrors)]
+pub async fn get_ingest_errors(token: String, limit: i64) -> Result<Vec<IngestError>, ServerFnError> {
+    use crate::db::ingest_errors::dsl;
+    crate::server::auth::require_admin(&token)?;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let errors = dsl::ingest_errors
+        .select(IngestError::as_select())
+        .order_by(dsl::created_at.desc())
+        .limit(limit)
+        .load(&mut connection)?;
+
+    Ok(errors)
+}
+
+/// Posts whose last ingestion attempt failed, retrying ones first.
+#[server(GetFailedItems)]
+pub async fn get_failed_items(token: String) -> Result<Vec<IngestItem>, ServerFnError> {
+    use crate::db::ingest_items::dsl;
+    crate::server::auth::require_admin(&token)?;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let items = dsl::ingest_items
+        .filter(dsl::status.ne(ItemStatus::Ingested))
+        .select(IngestItem::as_select())
+        .order_by((dsl::status.asc(), dsl::updated_at.desc()))
+        .load(&mut connection)?;
+
+    Ok(items)
+}
+
+#[server(GetNotificationDeliveries)]
+pub async fn get_notification_deliveries(token: String, limit: i64) -> Result<Vec<NotificationDelivery>, ServerFnError> {
+    use crate::db::notification_deliveries::dsl;
+    crate::server::auth::require_admin(&token)?;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let deliveries = dsl::notification_deliveries
+        .select(NotificationDelivery::as_select())
+        .order_by(dsl::created_at.desc())
+        .limit(limit)
+        .load(&mut connection)?;
+
+    Ok(deliveries)
+}
+
+#[server(PreviewSubmission)]
+pub async fn preview_submission(token: String, url_or_id: String) -> Result<(InsertSubmission, bool), ServerFnError> {
+    use crate::db::submissions::dsl;
+    crate::server::auth::require_admin(&token)?;
+    let FromContext::<ServerState>(state) = extract().await?;
+    let mut connection = state.pool.get()?;
+
+    let post_id = crate::server::extract_post_id(&url_or_id)
+        .ok_or(ServerFnError::new(format!("Could not find a post id in {}", url_or_id)))?;
+    let details = crate::server::fetch_story_details(&state, &post_id)
+        .await
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+    let already_present = dsl::submissions
+        .find(&details.guid)
+        .count()
+        .get_result::<i64>(&mut connection)?
+        > 0;
+
+    Ok((details, already_present))
+}
+
+#[server(AddSubmission)]
+pub async fn add_submission(token: String, post_id: String, add_category: Category) -> Result<(), ServerFnError> {
+    crate::server::auth::require_admin(&token)?;
+    let FromContext::<ServerState>(state) = extract().await?;
+
+    crate::server::add_post(&state, &post_id, add_category)
+        .await
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+
+    tracing::info!("Manually added submission {}.", post_id);
+    Ok(())
+}
+
+#[server(GetTopAuthors)]
+pub async fn get_top_authors(ranking: AuthorRanking, limit: i64) -> Result<Vec<AuthorTotals>, ServerFnError> {
+    use diesel::sql_types::{Array, BigInt, Text};
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let order = match ranking {
+        AuthorRanking::TotalClaps => "total_claps DESC, entries DESC",
+        AuthorRanking::Entries => "entries DESC, total_claps DESC",
     };
 
-    Ok((
-        latest_update_time,
-        latest_update_time.add(TimeDelta::minutes(15)),
+    let top_authors = diesel::sql_query(format!(
+        "SELECT a.id AS author_id, a.name, a.username, \
+                COALESCE(SUM(s.clap_count), 0) AS total_claps, \
+                COUNT(s.guid) AS entries, \
+                COUNT(DISTINCT s.contest_id) AS contests \
+         FROM authors a JOIN submissions s ON s.author_id = a.id \
+         WHERE s.username <> ALL($1) \
+         GROUP BY a.id \
+         ORDER BY {order} \
+         LIMIT $2"
     ))
+        .bind::<Array<Text>, _>(&settings.leaderboard.excluded_usernames)
+        .bind::<BigInt, _>(limit.clamp(1, MAX_TOP_AUTHORS))
+        .load::<AuthorTotals>(&mut connection)?;
+
+    Ok(top_authors)
+}
+
+/// Category wins per author in closed contests, by each contest's scoring strategy. Entries
+/// sharing the first rank share the win.
+#[server(GetCategoryWins)]
+pub async fn get_category_wins() -> Result<Vec<CategoryWins>, ServerFnError> {
+    use crate::db::authors::dsl as dsla;
+    use crate::db::contests::dsl as dslc;
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let closed = dslc::contests
+        .filter(dslc::ends_at.lt(Local::now()))
+        .select(Contest::as_select())
+        .load(&mut connection)?;
+
+    let mut winners: Vec<(String, Category)> = Vec::new();
+    for contest in &closed {
+        let ranked = crate::server::scoring::score_contest(&mut connection, &settings, contest)
+            .map_err(|err| ServerFnError::new(err.to_string()))?;
+        winners.extend(
+            ranked
+                .into_iter()
+                .filter(|entry| entry.rank == 1 && entry.submission.category != Category::None)
+                .filter_map(|entry| Some((entry.submission.author_id?, entry.submission.category))),
+        );
+    }
+
+    let author_ids: Vec<&String> = winners.iter().map(|(author_id, _)| author_id).collect();
+    let authors = dsla::authors
+        .filter(dsla::id.eq_any(author_ids))
+        .select((dsla::id, dsla::name, dsla::username))
+        .load::<(String, String, String)>(&mut connection)?;
+
+    let mut wins: Vec<CategoryWins> = Vec::new();
+    for (author_id, category) in winners {
+        if let Some(entry) = wins.iter_mut().find(|wins| wins.author_id == author_id && wins.category == category) {
+            entry.wins += 1;
+        } else if let Some((_, name, username)) = authors.iter().find(|(id, _, _)| *id == author_id) {
+            wins.push(CategoryWins {
+                author_id,
+                name: name.clone(),
+                username: username.clone(),
+                category,
+                wins: 1,
+            });
+        }
+    }
+    wins.sort_by(|a, b| b.wins.cmp(&a.wins).then_with(|| a.name.cmp(&b.name)));
+
+    Ok(wins)
+}
+
+#[server(GetContestParticipation)]
+pub async fn get_contest_participation() -> Result<Vec<ContestParticipation>, ServerFnError> {
+    use diesel::sql_types::{Array, Text};
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
+    let mut connection = pool.get()?;
+
+    let participation = diesel::sql_query(
+        "SELECT c.id AS contest_id, c.title, s.category, \
+                COUNT(s.guid) AS entries, \
+                COUNT(DISTINCT COALESCE(s.author_id, s.username)) AS authors, \
+                COALESCE(SUM(s.clap_count), 0) AS total_claps \
+         FROM contests c JOIN submissions s ON s.contest_id = c.id \
+         WHERE s.username <> ALL($1) \
+         GROUP BY c.id, s.category \
+         ORDER BY c.starts_at, s.category",
+    )
+        .bind::<Array<Text>, _>(&settings.leaderboard.excluded_usernames)
+        .load::<ContestParticipation>(&mut connection)?;
+
+    Ok(participation)
+}
+
+#[server(GetContestClapActivity)]
+pub async fn get_contest_clap_activity() -> Result<Vec<ContestClapActivity>, ServerFnError> {
+    use diesel::sql_types::{Array, Text};
+    let FromContext::<DbPool>(pool) = extract().await?;
+    let FromContext::<Arc<Settings>>(settings) = extract().await?;
+    let mut connection = pool.get()?;
+
+    // clap_history stores absolute counts, so the daily gain is the sum of the deltas between
+    // consecutive entries of each submission.
+    let activity = diesel::sql_query(
+        "SELECT c.id AS contest_id, c.title, DATE(h.timestamp) AS day, \
+                COALESCE(SUM(h.delta), 0)::INT8 AS claps_gained \
+         FROM ( \
+             SELECT guid, timestamp, \
+                    clap_count - LAG(clap_count) OVER (PARTITION BY guid ORDER BY timestamp) AS delta \
+             FROM clap_history \
+         ) h \
+         JOIN submissions s ON s.guid = h.guid \
+         JOIN contests c ON c.id = s.contest_id \
+         WHERE s.username <> ALL($1) \
+         GROUP BY c.id, day \
+         ORDER BY c.id, day",
+    )
+        .bind::<Array<Text>, _>(&settings.leaderboard.excluded_usernames)
+        .load::<ContestClapActivity>(&mut connection)?;
+
+    Ok(activity)
 }